@@ -77,6 +77,7 @@ fn main() {
                     nodcnt,
                     nodleft: actnodes,
                     itrcnt,
+                    ..
                 } => {
                     if nodcnt - lastnode >= 100.0 {
                         lastnode = nodcnt;
@@ -109,20 +110,26 @@ fn main() {
                     ..
                 } => {
                     println!("@MIPSol: ");
-                    let x = r#try!(ctx.get_solution(vars.as_slice()));
-                    println!(
-                        "**** New solution at node {}, obj {}, sol {}, x[0] = {} ****",
-                        nodcnt, obj, solcnt, x[0]
-                    );
+                    if let Context::MIPSol(sol) = ctx.context() {
+                        let x = r#try!(sol.solution(vars.as_slice()));
+                        println!(
+                            "**** New solution at node {}, obj {}, sol {}, x[0] = {} ****",
+                            nodcnt, obj, solcnt, x[0]
+                        );
+                    }
                 }
 
                 // Currently exploring a MIP node
                 MIPNode { .. } => {
                     println!("@MIPNode");
                     println!("**** NEW NODE! ****");
-                    let x = r#try!(ctx.get_node_rel(vars.as_slice()));
-                    println!("  relaxed solution = {:?}", x);
-                    r#try!(ctx.set_solution(vars.as_slice(), x.as_slice()));
+                    if let Context::MIPNode(node) = ctx.context() {
+                        if node.status() == Status::Optimal {
+                            let x = r#try!(node.node_rel(vars.as_slice()));
+                            println!("  relaxed solution = {:?}", x);
+                            r#try!(node.set_solution(vars.as_slice(), x.as_slice()));
+                        }
+                    }
                 }
 
                 // Currently in barrier
@@ -148,6 +155,8 @@ fn main() {
                     writer.write_all(message.as_bytes()).unwrap();
                     writer.write_all(&[b'\n']).unwrap();
                 }
+
+                _ => (),
             }
 
             Ok(())
@@ -52,6 +52,10 @@ fn main() {
         .unwrap();
     y.set(&mut model, attr::Obj, -1.0).unwrap();
 
+    assert!(model.get_pwl_obj(&y).unwrap().is_none());
+    let (px, _) = model.get_pwl_obj(&x).unwrap().unwrap();
+    assert_eq!(px.len(), n_points);
+
     optimize_and_print_status(&mut model).unwrap();
 
     // Negate piecewise-linear objective function for x.
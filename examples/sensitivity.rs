@@ -32,6 +32,12 @@ fn main() {
     // disable solver output for subsequent solvers.
     model.get_env_mut().set(param::OutputFlag, 0).unwrap();
 
+    // print the sensitivity ranging of the continuous model in which all integer variables are
+    // fixed at their optimal values.
+    let mut fixed = model.fixed().unwrap();
+    fixed.optimize().unwrap();
+    println!("{}", fixed.sensitivity().unwrap());
+
     // iterate through unfixed, binary variables in model
     let vars: Vec<_> = model.get_vars().cloned().collect();
     for (v, &orig_x) in vars.iter().zip(orig_sol.iter()) {
@@ -35,6 +35,14 @@ fn main() {
         .add_sos(&[x0.clone(), x2.clone()], &[1.0, 2.0], SOSType1)
         .unwrap();
 
+    // read back the members of each SOS constraint.
+    for sos in model.get_sos() {
+        let (sostype, members) = sos.members(&model).unwrap();
+        assert_eq!(sostype, SOSType1);
+        assert_eq!(members.len(), 2);
+        assert!(members[0].0 == x0);
+    }
+
     model.optimize().unwrap();
 
     model.write("sos.lp").unwrap();
@@ -18,14 +18,13 @@ fn main() {
 
     let status = model.status().unwrap();
     if status == Status::Infeasible {
-        model.compute_iis().unwrap();
+        let iis = model.compute_iis().unwrap();
 
         println!("The following constraint(s) cannot be satisfied:");
-        for c in model
-            .get_constrs()
-            .filter(|c| c.get(&model, attr::IISConstr).unwrap() != 0)
-        {
-            println!("  - {}", c.get(&model, attr::ConstrName).unwrap());
+        for c in iis.constrs.iter() {
+            println!("  - {}", c.name);
         }
+
+        iis.write("workforce1.ilp").unwrap();
     }
 }
@@ -24,7 +24,7 @@ pub struct GRBsvec {
 }
 
 
-#[derive(Debug,Copy,Clone)]
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
 pub enum IntParam {
   SolutionLimit,
   Method,
@@ -103,7 +103,7 @@ pub enum IntParam {
   Record
 }
 
-#[derive(Debug,Copy,Clone)]
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
 pub enum DoubleParam {
   Cutoff,
   IterationLimit,
@@ -131,7 +131,7 @@ pub enum DoubleParam {
   PreSOS2BigM
 }
 
-#[derive(Debug,Copy,Clone)]
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
 pub enum StringParam {
   LogFile,
   NodefileDir,
@@ -183,6 +183,11 @@ pub enum IntAttr {
   IISConstr,
   IISSOS,
   IISQConstr,
+  IISLBForce,
+  IISUBForce,
+  IISConstrForce,
+  IISSOSForce,
+  IISQConstrForce,
   TuneResultCount,
   Lazy,
   VarHintPri
@@ -560,6 +565,9 @@ extern "C" {
 
   pub fn GRBgettuneresult(model: *mut GRBmodel, n: c_int) -> c_int;
 
+  pub fn GRBtunemodels(nummodels: c_int, models: *mut *mut GRBmodel, ignore: *mut GRBmodel, hint: *mut GRBmodel)
+                       -> c_int;
+
   pub fn GRBgetdblparam(env: *mut GRBenv, paramname: c_str, value: *mut c_double) -> c_int;
 
   pub fn GRBgetintparam(env: *mut GRBenv, paramname: c_str, value: *mut c_int) -> c_int;
@@ -615,7 +623,11 @@ extern "C" {
                    lazysense: c_char, lazyrhs: c_double)
                    -> c_int;
 
-  pub fn GRBcbsolution(cbdata: *mut c_void, solution: *const c_double) -> c_int;
+  /// `objvalP` is ignored before Gurobi 8.0.
+  pub fn GRBcbsolution(cbdata: *mut c_void, solution: *const c_double, objvalP: *mut c_double) -> c_int;
+
+  /// Available since Gurobi 8.0.
+  pub fn GRBcbusesolution(cbdata: *mut c_void, objvalP: *mut c_double) -> c_int;
 
   pub fn GRBterminate(model: *mut GRBmodel);
 }
@@ -0,0 +1,401 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use itertools::{Itertools, Zip};
+
+use std::collections::btree_map::{self, BTreeMap};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use super::{Constr, Model, Var};
+use crate::attr;
+use crate::error::{Error, Result};
+use crate::INFINITY;
+
+/// Status of a variable or a constraint in a simplex basis
+///
+/// For constraints, only `Basic` and `AtLower` (nonbasic) are reported by Gurobi.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BasisStatus {
+    /// Basic
+    Basic,
+    /// Nonbasic at its lower bound
+    AtLower,
+    /// Nonbasic at its upper bound
+    AtUpper,
+    /// Superbasic (nonbasic, but not at one of its bounds)
+    SuperBasic,
+}
+
+impl TryFrom<i32> for BasisStatus {
+    type Error = Error;
+    fn try_from(val: i32) -> Result<BasisStatus> {
+        match val {
+            0 => Ok(BasisStatus::Basic),
+            -1 => Ok(BasisStatus::AtLower),
+            -2 => Ok(BasisStatus::AtUpper),
+            -3 => Ok(BasisStatus::SuperBasic),
+            _ => Err(Error::InvalidValue(format!("basis status {}", val))),
+        }
+    }
+}
+
+impl From<BasisStatus> for i32 {
+    fn from(status: BasisStatus) -> i32 {
+        match status {
+            BasisStatus::Basic => 0,
+            BasisStatus::AtLower => -1,
+            BasisStatus::AtUpper => -2,
+            BasisStatus::SuperBasic => -3,
+        }
+    }
+}
+
+/// A simplex basis keyed by the names of variables and constraints
+///
+/// Since the entries are identified by name, a basis captured by
+/// [`Model::basis()`](struct.Model.html#method.basis) can be applied to a modified model.
+/// Variables missing from the basis are treated as nonbasic at one of their bounds, and
+/// constraints missing from the basis are treated as basic.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Basis {
+    vars: BTreeMap<String, BasisStatus>,
+    constrs: BTreeMap<String, BasisStatus>,
+}
+
+impl Basis {
+    /// Create an empty basis.
+    pub fn new() -> Basis {
+        Basis::default()
+    }
+
+    /// Query the status of a variable.
+    pub fn var(&self, name: &str) -> Option<BasisStatus> {
+        self.vars.get(name).cloned()
+    }
+
+    /// Query the status of a linear constraint.
+    pub fn constr(&self, name: &str) -> Option<BasisStatus> {
+        self.constrs.get(name).cloned()
+    }
+
+    /// Set the status of a variable.
+    pub fn set_var(&mut self, name: &str, status: BasisStatus) {
+        self.vars.insert(name.to_owned(), status);
+    }
+
+    /// Set the status of a linear constraint.
+    pub fn set_constr(&mut self, name: &str, status: BasisStatus) {
+        self.constrs.insert(name.to_owned(), status);
+    }
+
+    /// Retrieve an iterator of the variable names and their status.
+    pub fn vars(&self) -> btree_map::Iter<String, BasisStatus> {
+        self.vars.iter()
+    }
+
+    /// Retrieve an iterator of the constraint names and their status.
+    pub fn constrs(&self) -> btree_map::Iter<String, BasisStatus> {
+        self.constrs.iter()
+    }
+
+    /// Read a basis from a file in the MPS basis (`.bas`) format.
+    pub fn read_from(filename: &str) -> Result<Basis> {
+        let file = r#try!(File::open(filename));
+        Basis::parse(BufReader::new(file))
+    }
+
+    /// Write the basis to a file in the MPS basis (`.bas`) format.
+    pub fn write(&self, filename: &str) -> Result<()> {
+        let file = r#try!(File::create(filename));
+        let mut writer = BufWriter::new(file);
+        r#try!(self.write_to(&mut writer));
+        r#try!(writer.flush());
+        Ok(())
+    }
+
+    /// Parse a basis in the MPS basis (`.bas`) format.
+    ///
+    /// Each line `XU x c` / `XL x c` marks the variable `x` as basic and the constraint `c` as
+    /// nonbasic at its upper/lower bound, and `UL x` / `LL x` marks the variable `x` as
+    /// nonbasic at its upper/lower bound.
+    pub fn parse<R: BufRead>(reader: R) -> Result<Basis> {
+        let mut basis = Basis::new();
+        for (lineno, line) in reader.lines().enumerate() {
+            let line = r#try!(line);
+            if line.starts_with('*') || line.trim().is_empty() {
+                continue;
+            }
+            if !line.starts_with(char::is_whitespace) {
+                // section headers
+                if line.starts_with("NAME") {
+                    continue;
+                } else if line.starts_with("ENDATA") {
+                    break;
+                }
+                return Err(Error::Parse(format!(
+                    "line {}: unknown section `{}`",
+                    lineno + 1,
+                    line
+                )));
+            }
+
+            let fields = line.split_whitespace().collect_vec();
+            match (fields[0], fields.len()) {
+                ("XU", 3) => {
+                    basis.set_var(fields[1], BasisStatus::Basic);
+                    basis.set_constr(fields[2], BasisStatus::AtUpper);
+                }
+                ("XL", 3) => {
+                    basis.set_var(fields[1], BasisStatus::Basic);
+                    basis.set_constr(fields[2], BasisStatus::AtLower);
+                }
+                ("UL", 2) => basis.set_var(fields[1], BasisStatus::AtUpper),
+                ("LL", 2) => basis.set_var(fields[1], BasisStatus::AtLower),
+                _ => {
+                    return Err(Error::Parse(format!(
+                        "line {}: invalid basis entry `{}`",
+                        lineno + 1,
+                        line.trim()
+                    )))
+                }
+            }
+        }
+        Ok(basis)
+    }
+
+    /// Write the basis in the MPS basis (`.bas`) format.
+    ///
+    /// Each basic variable is paired with a nonbasic constraint, so the numbers of them must be
+    /// equal. Superbasic variables cannot be represented in this format.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let basic = self
+            .vars
+            .iter()
+            .filter(|&(_, &s)| s == BasisStatus::Basic)
+            .collect_vec();
+        let nonbasic = self
+            .constrs
+            .iter()
+            .filter(|&(_, &s)| s != BasisStatus::Basic)
+            .collect_vec();
+        if let Some((var, _)) = self
+            .vars
+            .iter()
+            .find(|&(_, &s)| s == BasisStatus::SuperBasic)
+        {
+            return Err(Error::InvalidValue(format!(
+                "variable `{}` is superbasic, which cannot be written in the basis format",
+                var
+            )));
+        }
+        if basic.len() > nonbasic.len() {
+            return Err(Error::InvalidValue(format!(
+                "basic variable `{}` has no nonbasic constraint to be paired with",
+                basic[nonbasic.len()].0
+            )));
+        }
+        if nonbasic.len() > basic.len() {
+            return Err(Error::InvalidValue(format!(
+                "nonbasic constraint `{}` has no basic variable to be paired with",
+                nonbasic[basic.len()].0
+            )));
+        }
+
+        r#try!(writeln!(writer, "NAME"));
+        for ((var, _), (constr, &status)) in Zip::new((basic, nonbasic)) {
+            let code = if status == BasisStatus::AtUpper {
+                "XU"
+            } else {
+                "XL"
+            };
+            r#try!(writeln!(writer, " {} {} {}", code, var, constr));
+        }
+        for (var, &status) in self.vars.iter() {
+            match status {
+                BasisStatus::AtLower => r#try!(writeln!(writer, " LL {}", var)),
+                BasisStatus::AtUpper => r#try!(writeln!(writer, " UL {}", var)),
+                _ => {}
+            }
+        }
+        r#try!(writeln!(writer, "ENDATA"));
+        Ok(())
+    }
+}
+
+/// Primal and dual start vectors for a warm start of simplex, keyed by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WarmStart {
+    /// Primal values of the variables (`PStart`)
+    pub primal: BTreeMap<String, f64>,
+    /// Dual values of the linear constraints (`DStart`)
+    pub dual: BTreeMap<String, f64>,
+}
+
+impl Var {
+    /// Query the status of the variable in the current basis (`VBasis`).
+    pub fn basis_status(&self, model: &Model) -> Result<BasisStatus> {
+        BasisStatus::try_from(r#try!(self.get(model, attr::VBasis)))
+    }
+
+    /// Set the status of the variable in the warm start basis (`VBasis`).
+    pub fn set_basis_status(&self, model: &mut Model, status: BasisStatus) -> Result<()> {
+        self.set(model, attr::VBasis, status.into())
+    }
+}
+
+impl Constr {
+    /// Query the status of the constraint in the current basis (`CBasis`).
+    pub fn basis_status(&self, model: &Model) -> Result<BasisStatus> {
+        BasisStatus::try_from(r#try!(self.get(model, attr::CBasis)))
+    }
+
+    /// Set the status of the constraint in the warm start basis (`CBasis`).
+    pub fn set_basis_status(&self, model: &mut Model, status: BasisStatus) -> Result<()> {
+        self.set(model, attr::CBasis, status.into())
+    }
+}
+
+impl Model {
+    /// Capture the current simplex basis of the model.
+    ///
+    /// The model must have an optimal simplex basis (i.e. `VBasis` and `CBasis` are available).
+    pub fn basis(&self) -> Result<Basis> {
+        let vnames = r#try!(self.get_values(attr::VarName, self.vars.as_slice()));
+        let vbasis = r#try!(self.get_values(attr::VBasis, self.vars.as_slice()));
+        let cnames = r#try!(self.get_values(attr::ConstrName, self.constrs.as_slice()));
+        let cbasis = r#try!(self.get_values(attr::CBasis, self.constrs.as_slice()));
+
+        let mut basis = Basis::new();
+        for (name, status) in Zip::new((vnames, vbasis)) {
+            basis
+                .vars
+                .insert(name, r#try!(BasisStatus::try_from(status)));
+        }
+        for (name, status) in Zip::new((cnames, cbasis)) {
+            basis
+                .constrs
+                .insert(name, r#try!(BasisStatus::try_from(status)));
+        }
+        Ok(basis)
+    }
+
+    /// Apply a simplex basis to the model as a warm start of the next optimization.
+    ///
+    /// Variables missing from `basis` are set to nonbasic at one of their finite bounds (or
+    /// superbasic if both bounds are infinite), and constraints missing from `basis` are set to
+    /// basic.
+    pub fn set_basis(&mut self, basis: &Basis) -> Result<()> {
+        let vars = self.vars.clone();
+        let constrs = self.constrs.clone();
+
+        let vnames = r#try!(self.get_values(attr::VarName, vars.as_slice()));
+        let lbs = r#try!(self.get_values(attr::LB, vars.as_slice()));
+        let ubs = r#try!(self.get_values(attr::UB, vars.as_slice()));
+        let mut vbasis = Vec::with_capacity(vars.len());
+        for (name, lb, ub) in Zip::new((vnames, lbs, ubs)) {
+            let status = basis.var(&name).unwrap_or(if lb > -INFINITY {
+                BasisStatus::AtLower
+            } else if ub < INFINITY {
+                BasisStatus::AtUpper
+            } else {
+                BasisStatus::SuperBasic
+            });
+            vbasis.push(status.into());
+        }
+
+        let cnames = r#try!(self.get_values(attr::ConstrName, constrs.as_slice()));
+        let cbasis = cnames
+            .iter()
+            .map(|name| match basis.constr(name) {
+                Some(BasisStatus::Basic) | None => 0,
+                Some(_) => -1,
+            })
+            .collect_vec();
+
+        r#try!(self.set_values(attr::VBasis, vars.as_slice(), vbasis.as_slice()));
+        self.set_values(attr::CBasis, constrs.as_slice(), cbasis.as_slice())
+    }
+
+    /// Capture the current primal and dual solution as a warm start.
+    pub fn warm_start(&self) -> Result<WarmStart> {
+        let vnames = r#try!(self.get_values(attr::VarName, self.vars.as_slice()));
+        let x = r#try!(self.get_values(attr::X, self.vars.as_slice()));
+        let cnames = r#try!(self.get_values(attr::ConstrName, self.constrs.as_slice()));
+        let pi = r#try!(self.get_values(attr::Pi, self.constrs.as_slice()));
+
+        Ok(WarmStart {
+            primal: Zip::new((vnames, x)).collect(),
+            dual: Zip::new((cnames, pi)).collect(),
+        })
+    }
+
+    /// Set the primal/dual start vectors (`PStart` and `DStart`) of the model.
+    ///
+    /// Variables and constraints missing from `start` are left unchanged.
+    pub fn set_warm_start(&mut self, start: &WarmStart) -> Result<()> {
+        let vars = self.vars.clone();
+        let constrs = self.constrs.clone();
+
+        let vnames = r#try!(self.get_values(attr::VarName, vars.as_slice()));
+        let cnames = r#try!(self.get_values(attr::ConstrName, constrs.as_slice()));
+        let vindex: HashMap<_, _> = vnames.into_iter().zip(vars).collect();
+        let cindex: HashMap<_, _> = cnames.into_iter().zip(constrs).collect();
+
+        let (mut vars, mut pstart) = (Vec::new(), Vec::new());
+        for (name, &val) in start.primal.iter() {
+            if let Some(v) = vindex.get(name) {
+                vars.push(v.clone());
+                pstart.push(val);
+            }
+        }
+
+        let (mut constrs, mut dstart) = (Vec::new(), Vec::new());
+        for (name, &val) in start.dual.iter() {
+            if let Some(c) = cindex.get(name) {
+                constrs.push(c.clone());
+                dstart.push(val);
+            }
+        }
+
+        r#try!(self.set_values(attr::PStart, vars.as_slice(), pstart.as_slice()));
+        self.set_values(attr::DStart, constrs.as_slice(), dstart.as_slice())
+    }
+}
+
+#[test]
+fn basis_file_roundtrip() {
+    let text = "NAME\n XU x1 c1\n XL x2 c2\n UL x3\n LL x4\nENDATA\n";
+    let basis = Basis::parse(text.as_bytes()).unwrap();
+    assert_eq!(basis.var("x1"), Some(BasisStatus::Basic));
+    assert_eq!(basis.constr("c1"), Some(BasisStatus::AtUpper));
+    assert_eq!(basis.constr("c2"), Some(BasisStatus::AtLower));
+    assert_eq!(basis.var("x3"), Some(BasisStatus::AtUpper));
+    assert_eq!(basis.var("x5"), None);
+
+    let mut buf = Vec::new();
+    basis.write_to(&mut buf).unwrap();
+    assert_eq!(Basis::parse(buf.as_slice()).unwrap(), basis);
+}
+
+#[test]
+fn basis_write_should_name_unpaired_entries() {
+    let mut basis = Basis::parse("NAME\n XU x1 c1\nENDATA\n".as_bytes()).unwrap();
+    basis.set_var("x2", BasisStatus::Basic);
+    match basis.write_to(&mut Vec::new()) {
+        Err(Error::InvalidValue(msg)) => assert!(msg.contains("`x2`")),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    basis.set_var("x2", BasisStatus::SuperBasic);
+    match basis.write_to(&mut Vec::new()) {
+        Err(Error::InvalidValue(msg)) => assert!(msg.contains("`x2`")),
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    assert!(BasisStatus::try_from(-4).is_err());
+}
@@ -7,14 +7,16 @@ use crate::ffi;
 use itertools::{Itertools, Zip};
 
 use std::mem::transmute;
-use std::ops::Deref;
 use std::os::raw;
 use std::ptr::null;
+use std::slice::Iter;
 
-use crate::error::{Error, Result};
+use crate::env::Env;
+use crate::error::{Error, Result, DATA_NOT_AVAILABLE};
 use crate::model::expr::LinExpr;
-use crate::model::{ConstrSense, Model, Var};
+use crate::model::{Constr, ConstrSense, Model, QConstr, Status, Var, SOS};
 use crate::util;
+use crate::{INFINITY, UNDEFINED};
 
 // Location where the callback called.
 const POLLING: i32 = 0;
@@ -25,6 +27,8 @@ const MIPSOL: i32 = 4;
 const MIPNODE: i32 = 5;
 const MESSAGE: i32 = 6;
 const BARRIER: i32 = 7;
+const MULTIOBJ: i32 = 8;
+const IIS: i32 = 9;
 
 const PRE_COLDEL: i32 = 1000;
 const PRE_ROWDEL: i32 = 1001;
@@ -45,8 +49,12 @@ const MIP_SOLCNT: i32 = 3003;
 const MIP_CUTCNT: i32 = 3004;
 const MIP_NODLFT: i32 = 3005;
 const MIP_ITRCNT: i32 = 3006;
-#[allow(dead_code)]
+// until Gurobi 8.x
 const MIP_OBJBNDC: i32 = 3007;
+// since Gurobi 9.0
+const MIP_OPENSCENARIOS: i32 = 3007;
+// since Gurobi 9.5
+const MIP_PHASE: i32 = 3008;
 
 const MIPSOL_SOL: i32 = 4001;
 const MIPSOL_OBJ: i32 = 4002;
@@ -54,8 +62,14 @@ const MIPSOL_OBJBST: i32 = 4003;
 const MIPSOL_OBJBND: i32 = 4004;
 const MIPSOL_NODCNT: i32 = 4005;
 const MIPSOL_SOLCNT: i32 = 4006;
-#[allow(dead_code)]
+// until Gurobi 8.x
 const MIPSOL_OBJBNDC: i32 = 4007;
+// since Gurobi 9.0
+const MIPSOL_OPENSCENARIOS: i32 = 4007;
+// since Gurobi 9.5
+const MIPSOL_PHASE: i32 = 4008;
+// since Gurobi 12.0
+const MIPSOL_NODLFT: i32 = 4009;
 
 const MIPNODE_STATUS: i32 = 5001;
 const MIPNODE_REL: i32 = 5002;
@@ -63,13 +77,21 @@ const MIPNODE_OBJBST: i32 = 5003;
 const MIPNODE_OBJBND: i32 = 5004;
 const MIPNODE_NODCNT: i32 = 5005;
 const MIPNODE_SOLCNT: i32 = 5006;
-#[allow(dead_code)]
+// until Gurobi 8.x
 const MIPNODE_BRVAR: i32 = 5007;
-#[allow(dead_code)]
+// until Gurobi 8.x
 const MIPNODE_OBJBNDC: i32 = 5008;
+// since Gurobi 9.0
+const MIPNODE_OPENSCENARIOS: i32 = 5008;
+// since Gurobi 9.5
+const MIPNODE_PHASE: i32 = 5009;
+// since Gurobi 12.0
+const MIPNODE_NODLFT: i32 = 5010;
 
 const MSG_STRING: i32 = 6001;
 const RUNTIME: i32 = 6002;
+// since Gurobi 9.5
+const WORK: i32 = 6003;
 
 const BARRIER_ITRCNT: i32 = 7001;
 const BARRIER_PRIMOBJ: i32 = 7002;
@@ -78,10 +100,24 @@ const BARRIER_PRIMINF: i32 = 7004;
 const BARRIER_DUALINF: i32 = 7005;
 const BARRIER_COMPL: i32 = 7006;
 
+const MULTIOBJ_OBJCNT: i32 = 8001;
+const MULTIOBJ_SOLCNT: i32 = 8002;
+const MULTIOBJ_SOL: i32 = 8003;
+
+const IIS_CONSTRMIN: i32 = 9001;
+const IIS_CONSTRMAX: i32 = 9002;
+const IIS_CONSTRGUESS: i32 = 9003;
+const IIS_BOUNDMIN: i32 = 9004;
+const IIS_BOUNDMAX: i32 = 9005;
+const IIS_BOUNDGUESS: i32 = 9006;
+
 /// Location where the callback called
 ///
 /// If you want to get more information, see [official
 /// manual](https://www.gurobi.com/documentation/6.5/refman/callback_codes.html).
+///
+/// Some fields depend on the version of the linked Gurobi library; they are `None` when the
+/// library does not provide them.
 #[derive(Debug, Clone)]
 pub enum Where {
     /// Periodic polling callback
@@ -131,6 +167,14 @@ pub enum Where {
         nodleft: f64,
         /// Current simplex iteration count.
         itrcnt: f64,
+        /// Current objective bound with cuts (until Gurobi 8.x).
+        objbndc: Option<f64>,
+        /// Number of scenarios still open in a multi-scenario model (since Gurobi 9.0).
+        openscenarios: Option<i32>,
+        /// Current phase of the MIP solution (since Gurobi 9.5).
+        phase: Option<i32>,
+        /// Current work spent on the optimization (since Gurobi 9.5).
+        work: Option<f64>,
     },
 
     /// Found a new MIP incumbent
@@ -145,6 +189,16 @@ pub enum Where {
         nodcnt: f64,
         /// Current count of feasible solutions found.
         solcnt: f64,
+        /// Current objective bound with cuts (until Gurobi 8.x).
+        objbndc: Option<f64>,
+        /// Number of scenarios still open in a multi-scenario model (since Gurobi 9.0).
+        openscenarios: Option<i32>,
+        /// Current phase of the MIP solution (since Gurobi 9.5).
+        phase: Option<i32>,
+        /// Current work spent on the optimization (since Gurobi 9.5).
+        work: Option<f64>,
+        /// Current unexplored node count (since Gurobi 12.0).
+        nodleft: Option<f64>,
     },
 
     /// Currently exploring a MIP node
@@ -159,6 +213,18 @@ pub enum Where {
         nodcnt: f64,
         /// Current count of feasible solutions found.
         solcnt: i32,
+        /// Index of the branching variable at the current node (until Gurobi 8.x).
+        brvar: Option<i32>,
+        /// Current objective bound with cuts (until Gurobi 8.x).
+        objbndc: Option<f64>,
+        /// Number of scenarios still open in a multi-scenario model (since Gurobi 9.0).
+        openscenarios: Option<i32>,
+        /// Current phase of the MIP solution (since Gurobi 9.5).
+        phase: Option<i32>,
+        /// Current work spent on the optimization (since Gurobi 9.5).
+        work: Option<f64>,
+        /// Current unexplored node count (since Gurobi 12.0).
+        nodleft: Option<f64>,
     },
 
     /// Printing a log message
@@ -179,6 +245,33 @@ pub enum Where {
         /// Complementarity violation for current barrier iterate.
         compl: f64,
     },
+
+    /// Solving a multi-objective model
+    MultiObj {
+        /// Current count of objectives already optimized.
+        objcnt: i32,
+        /// Current count of feasible solutions found.
+        solcnt: i32,
+    },
+
+    /// Computing an IIS
+    IIS {
+        /// Minimum number of constraints in the IIS.
+        constrmin: i32,
+        /// Maximum number of constraints in the IIS.
+        constrmax: i32,
+        /// Estimated number of constraints in the IIS.
+        constrguess: i32,
+        /// Minimum number of variable bounds in the IIS.
+        boundmin: i32,
+        /// Maximum number of variable bounds in the IIS.
+        boundmax: i32,
+        /// Estimated number of variable bounds in the IIS.
+        boundguess: i32,
+    },
+
+    /// A location which is not known to this crate
+    Unknown(i32),
 }
 
 impl Into<i32> for Where {
@@ -192,11 +285,35 @@ impl Into<i32> for Where {
             Where::MIPNode { .. } => MIPNODE,
             Where::Message(_) => MESSAGE,
             Where::Barrier { .. } => BARRIER,
+            Where::MultiObj { .. } => MULTIOBJ,
+            Where::IIS { .. } => IIS,
+            Where::Unknown(where_) => where_,
+        }
+    }
+}
+
+// callback codes which depend on the version of the linked library.
+#[derive(Debug, PartialEq)]
+struct Availability {
+    until_8: bool,
+    since_9_5: bool,
+    since_12: bool,
+}
+
+impl Availability {
+    fn new(major: i32, minor: i32) -> Availability {
+        Availability {
+            until_8: major < 9,
+            since_9_5: (major, minor) >= (9, 5),
+            since_12: major >= 12,
         }
     }
 }
 
 /// The context object for Gurobi callback.
+///
+/// The operations which are only valid at certain locations are provided by the
+/// location-specific contexts (see [`context()`](#method.context)).
 pub struct Callback<'a> {
     cbdata: *mut ffi::c_void,
     where_: Where,
@@ -215,6 +332,13 @@ impl<'a> New<'a> for Callback<'a> {
             model: model,
         };
 
+        let (major, minor, _) = crate::version();
+        let Availability {
+            until_8,
+            since_9_5,
+            since_12,
+        } = Availability::new(major, minor);
+
         let where_ = match where_ {
             POLLING => Where::Polling,
             PRESOLVE => Where::PreSolve {
@@ -240,6 +364,10 @@ impl<'a> New<'a> for Callback<'a> {
                 cutcnt: r#try!(callback.get_int(MIP, MIP_CUTCNT)),
                 nodleft: r#try!(callback.get_double(MIP, MIP_NODLFT)),
                 itrcnt: r#try!(callback.get_double(MIP, MIP_ITRCNT)),
+                objbndc: callback.get_double_if(until_8, MIP, MIP_OBJBNDC),
+                openscenarios: callback.get_int_if(!until_8, MIP, MIP_OPENSCENARIOS),
+                phase: callback.get_int_if(since_9_5, MIP, MIP_PHASE),
+                work: callback.get_double_if(since_9_5, MIP, WORK),
             },
             MIPSOL => Where::MIPSol {
                 obj: r#try!(callback.get_double(MIPSOL, MIPSOL_OBJ)),
@@ -247,6 +375,11 @@ impl<'a> New<'a> for Callback<'a> {
                 objbnd: r#try!(callback.get_double(MIPSOL, MIPSOL_OBJBND)),
                 nodcnt: r#try!(callback.get_double(MIPSOL, MIPSOL_NODCNT)),
                 solcnt: r#try!(callback.get_double(MIPSOL, MIPSOL_SOLCNT)),
+                objbndc: callback.get_double_if(until_8, MIPSOL, MIPSOL_OBJBNDC),
+                openscenarios: callback.get_int_if(!until_8, MIPSOL, MIPSOL_OPENSCENARIOS),
+                phase: callback.get_int_if(since_9_5, MIPSOL, MIPSOL_PHASE),
+                work: callback.get_double_if(since_9_5, MIPSOL, WORK),
+                nodleft: callback.get_double_if(since_12, MIPSOL, MIPSOL_NODLFT),
             },
             MIPNODE => Where::MIPNode {
                 status: r#try!(callback.get_int(MIPNODE, MIPNODE_STATUS)),
@@ -254,6 +387,12 @@ impl<'a> New<'a> for Callback<'a> {
                 objbnd: r#try!(callback.get_double(MIPNODE, MIPNODE_OBJBND)),
                 nodcnt: r#try!(callback.get_double(MIPNODE, MIPNODE_NODCNT)),
                 solcnt: r#try!(callback.get_int(MIPNODE, MIPNODE_SOLCNT)),
+                brvar: callback.get_int_if(until_8, MIPNODE, MIPNODE_BRVAR),
+                objbndc: callback.get_double_if(until_8, MIPNODE, MIPNODE_OBJBNDC),
+                openscenarios: callback.get_int_if(!until_8, MIPNODE, MIPNODE_OPENSCENARIOS),
+                phase: callback.get_int_if(since_9_5, MIPNODE, MIPNODE_PHASE),
+                work: callback.get_double_if(since_9_5, MIPNODE, WORK),
+                nodleft: callback.get_double_if(since_12, MIPNODE, MIPNODE_NODLFT),
             },
             MESSAGE => Where::Message(
                 r#try!(callback.get_string(MESSAGE, MSG_STRING))
@@ -268,7 +407,19 @@ impl<'a> New<'a> for Callback<'a> {
                 dualinf: r#try!(callback.get_double(BARRIER, BARRIER_DUALINF)),
                 compl: r#try!(callback.get_double(BARRIER, BARRIER_COMPL)),
             },
-            _ => panic!("Invalid callback location. {}", where_),
+            MULTIOBJ => Where::MultiObj {
+                objcnt: r#try!(callback.get_int(MULTIOBJ, MULTIOBJ_OBJCNT)),
+                solcnt: r#try!(callback.get_int(MULTIOBJ, MULTIOBJ_SOLCNT)),
+            },
+            IIS => Where::IIS {
+                constrmin: r#try!(callback.get_int(IIS, IIS_CONSTRMIN)),
+                constrmax: r#try!(callback.get_int(IIS, IIS_CONSTRMAX)),
+                constrguess: r#try!(callback.get_int(IIS, IIS_CONSTRGUESS)),
+                boundmin: r#try!(callback.get_int(IIS, IIS_BOUNDMIN)),
+                boundmax: r#try!(callback.get_int(IIS, IIS_BOUNDMAX)),
+                boundguess: r#try!(callback.get_int(IIS, IIS_BOUNDGUESS)),
+            },
+            where_ => Where::Unknown(where_),
         };
 
         callback.where_ = where_;
@@ -282,32 +433,65 @@ impl<'a> Callback<'a> {
         self.where_.clone()
     }
 
+    /// Retrieve the context of the current location.
+    ///
+    /// # Example
+    /// ```ignore
+    /// model.optimize_with_callback(|cb| {
+    ///     match cb.context() {
+    ///         Context::MIPSol(ctx) => {
+    ///             let x = ctx.solution(&vars)?;
+    ///             // ...
+    ///             ctx.add_lazy(expr, Less, 1.0)?;
+    ///         }
+    ///         Context::Message(ctx) => print!("{}", ctx.message()),
+    ///         _ => (),
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub fn context(&self) -> Context<'_> {
+        match self.where_ {
+            Where::MIPSol { .. } => Context::MIPSol(MipSolCtx { cb: self }),
+            Where::MIPNode { .. } => Context::MIPNode(MipNodeCtx { cb: self }),
+            Where::Message(_) => Context::Message(MessageCtx { cb: self }),
+            Where::MultiObj { .. } => Context::MultiObj(MultiObjCtx { cb: self }),
+            _ => Context::Progress(ProgressCtx { cb: self }),
+        }
+    }
+
+    /// Retrieve a read-only view of the model being solved.
+    pub fn model(&self) -> ModelView<'a> {
+        ModelView { model: self.model }
+    }
+
+    /// Send a request to terminate the current optimization.
+    pub fn terminate(&self) {
+        self.model.terminate()
+    }
+
     /// Retrive node relaxation solution values at the current node.
+    #[deprecated(note = "use `MipNodeCtx::node_rel()` from `Callback::context()` instead")]
     pub fn get_node_rel(&self, vars: &[Var]) -> Result<Vec<f64>> {
-        // memo: only MIPNode && status == Optimal
-        self.get_double_array(MIPNODE, MIPNODE_REL)
-            .map(|buf| vars.iter().map(|v| buf[v.index() as usize]).collect_vec())
+        self.node_rel(vars)
     }
 
     /// Retrieve values from the current solution vector.
+    #[deprecated(note = "use `MipSolCtx::solution()` from `Callback::context()` instead")]
     pub fn get_solution(&self, vars: &[Var]) -> Result<Vec<f64>> {
-        self.get_double_array(MIPSOL, MIPSOL_SOL)
-            .map(|buf| vars.iter().map(|v| buf[v.index() as usize]).collect_vec())
+        self.solution(vars)
+    }
+
+    /// Retrieve values from the new solution of a multi-objective model.
+    #[deprecated(note = "use `MultiObjCtx::solution()` from `Callback::context()` instead")]
+    pub fn get_multiobj_solution(&self, vars: &[Var]) -> Result<Vec<f64>> {
+        self.multiobj_solution(vars)
     }
 
     /// Provide a new feasible solution for a MIP model.
+    #[deprecated(note = "use `MipNodeCtx::set_solution()` from `Callback::context()` instead")]
     pub fn set_solution(&self, vars: &[Var], solution: &[f64]) -> Result<()> {
-        if vars.len() != solution.len() || vars.len() < self.model.vars.len() {
-            return Err(Error::InconsitentDims);
-        }
-
-        let mut buf = vec![0.0; self.model.vars.len()];
-        for (v, &sol) in Zip::new((vars.iter(), solution.iter())) {
-            let i = v.index() as usize;
-            buf[i] = sol;
-        }
-
-        self.check_apicall(unsafe { ffi::GRBcbsolution(self.cbdata, buf.as_ptr()) })
+        self.provide_solution(vars, solution).map(|_| ())
     }
 
     /// Retrieve the elapsed solver runtime [sec].
@@ -318,8 +502,69 @@ impl<'a> Callback<'a> {
         self.get_double(self.get_where().into(), RUNTIME)
     }
 
+    /// Retrieve the elapsed solver work [work units] (since Gurobi 9.5).
+    pub fn get_work(&self) -> Result<f64> {
+        if let Where::Polling = self.get_where() {
+            return Err(Error::FromAPI("bad call in callback".to_owned(), 40001));
+        }
+        self.get_double(self.get_where().into(), WORK)
+    }
+
     /// Add a new cutting plane to the MIP model.
+    #[deprecated(note = "use `MipNodeCtx::add_cut()` from `Callback::context()` instead")]
     pub fn add_cut(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
+        self.cut(lhs, sense, rhs)
+    }
+
+    /// Add a new lazy constraint to the MIP model.
+    #[deprecated(
+        note = "use `add_lazy()` of `MipSolCtx` or `MipNodeCtx` from `Callback::context()` instead"
+    )]
+    pub fn add_lazy(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
+        self.lazy(lhs, sense, rhs)
+    }
+
+    fn node_rel(&self, vars: &[Var]) -> Result<Vec<f64>> {
+        // memo: only MIPNode && status == Optimal
+        self.get_double_array(MIPNODE, MIPNODE_REL)
+            .map(|buf| vars.iter().map(|v| buf[v.index() as usize]).collect_vec())
+    }
+
+    fn solution(&self, vars: &[Var]) -> Result<Vec<f64>> {
+        self.get_double_array(MIPSOL, MIPSOL_SOL)
+            .map(|buf| vars.iter().map(|v| buf[v.index() as usize]).collect_vec())
+    }
+
+    fn multiobj_solution(&self, vars: &[Var]) -> Result<Vec<f64>> {
+        self.get_double_array(MULTIOBJ, MULTIOBJ_SOL)
+            .map(|buf| vars.iter().map(|v| buf[v.index() as usize]).collect_vec())
+    }
+
+    fn provide_solution(&self, vars: &[Var], solution: &[f64]) -> Result<Option<f64>> {
+        let buf = r#try!(solution_buffer(self.model.vars.len(), vars, solution));
+
+        let mut objval = INFINITY;
+        r#try!(self
+            .check_apicall(unsafe { ffi::GRBcbsolution(self.cbdata, buf.as_ptr(), &mut objval) }));
+
+        // the objective value is only reported since Gurobi 8.0. a partial solution is completed
+        // later unless it is used immediately, which is only possible at `MIPNode`.
+        let (major, _, _) = crate::version();
+        if major < 8 {
+            return Ok(None);
+        }
+        if let Where::MIPNode { .. } = self.where_ {
+            objval = INFINITY;
+            r#try!(self.check_apicall(unsafe { ffi::GRBcbusesolution(self.cbdata, &mut objval) }));
+        }
+        if objval < INFINITY {
+            Ok(Some(objval))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn cut(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
         let (vars, coeff, offset) = lhs.into();
         self.check_apicall(unsafe {
             ffi::GRBcbcut(
@@ -333,8 +578,7 @@ impl<'a> Callback<'a> {
         })
     }
 
-    /// Add a new lazy constraint to the MIP model.
-    pub fn add_lazy(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
+    fn lazy(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
         let (vars, coeff, offset) = lhs.into();
         self.check_apicall(unsafe {
             ffi::GRBcblazy(
@@ -374,6 +618,23 @@ impl<'a> Callback<'a> {
         .and(Ok(buf.into()))
     }
 
+    // query an optional value, which is only available in some versions of Gurobi.
+    fn get_int_if(&self, available: bool, where_: i32, what: i32) -> Option<i32> {
+        if available {
+            self.get_int(where_, what).ok()
+        } else {
+            None
+        }
+    }
+
+    fn get_double_if(&self, available: bool, where_: i32, what: i32) -> Option<f64> {
+        if available {
+            self.get_double(where_, what).ok()
+        } else {
+            None
+        }
+    }
+
     fn get_double_array(&self, where_: i32, what: i32) -> Result<Vec<f64>> {
         let mut buf = vec![0.0; self.model.vars.len()];
         self.check_apicall(unsafe {
@@ -403,9 +664,290 @@ impl<'a> Callback<'a> {
     }
 }
 
-impl<'a> Deref for Callback<'a> {
-    type Target = Model;
-    fn deref(&self) -> &Model {
-        self.model
+/// Read-only view of the model being solved
+///
+/// Querying or modifying the model through the API is not allowed during the optimization, so
+/// only the information kept on the Rust side is available.
+#[derive(Clone, Copy)]
+pub struct ModelView<'a> {
+    model: &'a Model,
+}
+
+impl<'a> ModelView<'a> {
+    /// Retrieve the variables of the model.
+    pub fn get_vars(&self) -> Iter<'a, Var> {
+        self.model.vars.iter()
+    }
+
+    /// Retrieve the linear constraints of the model.
+    pub fn get_constrs(&self) -> Iter<'a, Constr> {
+        self.model.constrs.iter()
+    }
+
+    /// Retrieve the quadratic constraints of the model.
+    pub fn get_qconstrs(&self) -> Iter<'a, QConstr> {
+        self.model.qconstrs.iter()
+    }
+
+    /// Retrieve the SOS constraints of the model.
+    pub fn get_sos(&self) -> Iter<'a, SOS> {
+        self.model.sos.iter()
+    }
+
+    /// Retrieve the environment of the model, e.g. to query parameters.
+    pub fn get_env(&self) -> &'a Env {
+        &self.model.env
+    }
+}
+
+/// Context of a callback, distinguished by its location
+///
+/// See [`Callback::context()`](struct.Callback.html#method.context).
+pub enum Context<'a> {
+    /// Found a new MIP incumbent
+    MIPSol(MipSolCtx<'a>),
+    /// Currently exploring a MIP node
+    MIPNode(MipNodeCtx<'a>),
+    /// Printing a log message
+    Message(MessageCtx<'a>),
+    /// Found a new solution of a multi-objective model
+    MultiObj(MultiObjCtx<'a>),
+    /// Other locations, which only report the progress of the optimization
+    Progress(ProgressCtx<'a>),
+}
+
+/// Context at `MIPSol`
+pub struct MipSolCtx<'a> {
+    cb: &'a Callback<'a>,
+}
+
+/// Context at `MIPNode`
+pub struct MipNodeCtx<'a> {
+    cb: &'a Callback<'a>,
+}
+
+/// Context at `Message`
+pub struct MessageCtx<'a> {
+    cb: &'a Callback<'a>,
+}
+
+/// Context at `MultiObj`
+pub struct MultiObjCtx<'a> {
+    cb: &'a Callback<'a>,
+}
+
+/// Context at the locations which only report progress information
+pub struct ProgressCtx<'a> {
+    cb: &'a Callback<'a>,
+}
+
+macro_rules! impl_common_ctx {
+    ($($t:ident)*) => {$(
+        impl<'a> $t<'a> {
+            /// Retrieve the progress information at the location.
+            pub fn info(&self) -> &Where {
+                &self.cb.where_
+            }
+
+            /// Retrieve a read-only view of the model being solved.
+            pub fn model(&self) -> ModelView<'a> {
+                self.cb.model()
+            }
+
+            /// Retrieve the elapsed solver runtime [sec].
+            pub fn runtime(&self) -> Result<f64> {
+                self.cb.get_runtime()
+            }
+
+            /// Send a request to terminate the current optimization.
+            pub fn terminate(&self) {
+                self.cb.terminate()
+            }
+        }
+    )*};
+}
+
+impl_common_ctx! { MipSolCtx MipNodeCtx MessageCtx MultiObjCtx ProgressCtx }
+
+impl<'a> MipSolCtx<'a> {
+    /// Retrieve values of the new incumbent solution.
+    pub fn solution(&self, vars: &[Var]) -> Result<Vec<f64>> {
+        self.cb.solution(vars)
+    }
+
+    /// Objective value of the new incumbent solution.
+    pub fn obj(&self) -> f64 {
+        match self.cb.where_ {
+            Where::MIPSol { obj, .. } => obj,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Number of explored nodes when the solution was found.
+    pub fn node_count(&self) -> f64 {
+        match self.cb.where_ {
+            Where::MIPSol { nodcnt, .. } => nodcnt,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Add a new lazy constraint to cut off the new solution.
+    pub fn add_lazy(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
+        self.cb.lazy(lhs, sense, rhs)
+    }
+}
+
+impl<'a> MipNodeCtx<'a> {
+    /// Optimization status of the node relaxation.
+    pub fn status(&self) -> Status {
+        match self.cb.where_ {
+            Where::MIPNode { status, .. } => status.into(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Number of explored nodes.
+    pub fn node_count(&self) -> f64 {
+        match self.cb.where_ {
+            Where::MIPNode { nodcnt, .. } => nodcnt,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Retrieve values of the node relaxation solution.
+    ///
+    /// It is only available when the node relaxation is solved to optimality.
+    pub fn node_rel(&self, vars: &[Var]) -> Result<Vec<f64>> {
+        if self.status() != Status::Optimal {
+            return Err(Error::FromAPI(
+                "node relaxation solution is not available".to_owned(),
+                DATA_NOT_AVAILABLE,
+            ));
+        }
+        self.cb.node_rel(vars)
+    }
+
+    /// Add a new cutting plane to the node.
+    pub fn add_cut(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
+        self.cb.cut(lhs, sense, rhs)
+    }
+
+    /// Add a new lazy constraint to the node.
+    pub fn add_lazy(&self, lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Result<()> {
+        self.cb.lazy(lhs, sense, rhs)
+    }
+
+    /// Provide a new feasible solution.
+    ///
+    /// The solution may be partial: variables which are not given (or given
+    /// [`UNDEFINED`](constant.UNDEFINED.html)) are completed by Gurobi. Since Gurobi 8.0, the
+    /// values are used immediately (`GRBcbusesolution()`), and the objective value is returned
+    /// if Gurobi found a new solution from them.
+    pub fn set_solution(&self, vars: &[Var], solution: &[f64]) -> Result<Option<f64>> {
+        self.cb.provide_solution(vars, solution)
+    }
+}
+
+impl<'a> MessageCtx<'a> {
+    /// The log message.
+    pub fn message(&self) -> &str {
+        match self.cb.where_ {
+            Where::Message(ref message) => message.as_str(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> MultiObjCtx<'a> {
+    /// Retrieve values of the new solution.
+    pub fn solution(&self, vars: &[Var]) -> Result<Vec<f64>> {
+        self.cb.multiobj_solution(vars)
+    }
+}
+
+// values of all variables, where the variables not in `vars` are left to Gurobi.
+fn solution_buffer(numvars: usize, vars: &[Var], solution: &[f64]) -> Result<Vec<f64>> {
+    if vars.len() != solution.len() || vars.len() > numvars {
+        return Err(Error::InconsitentDims);
     }
+
+    let mut buf = vec![UNDEFINED; numvars];
+    for (v, &sol) in Zip::new((vars.iter(), solution.iter())) {
+        let i = v.index();
+        if i < 0 || i as usize >= numvars {
+            return Err(Error::InconsitentDims);
+        }
+        buf[i as usize] = sol;
+    }
+    Ok(buf)
+}
+
+// run `f` with a callback on a model which owns no Gurobi objects, to test code which does not
+// call the library.
+#[cfg(test)]
+pub(crate) fn with_detached_callback<R, F: FnOnce(&Callback) -> R>(where_: Where, f: F) -> R {
+    use crate::env::FromRaw;
+    use crate::model::InterruptHandle;
+    use std::mem::ManuallyDrop;
+    use std::ptr::null_mut;
+
+    let model = ManuallyDrop::new(Model {
+        model: null_mut(),
+        env: Env::from_raw(null_mut()),
+        updatemode: None,
+        vars: Vec::new(),
+        constrs: Vec::new(),
+        qconstrs: Vec::new(),
+        sos: Vec::new(),
+        interrupt: InterruptHandle::new(null_mut()),
+    });
+    let callback = Callback {
+        cbdata: null_mut(),
+        where_,
+        model: &model,
+    };
+    f(&callback)
+}
+
+#[test]
+fn callback_codes_should_be_gated_by_version() {
+    let gates = |major, minor| {
+        let a = Availability::new(major, minor);
+        (a.until_8, a.since_9_5, a.since_12)
+    };
+    assert_eq!(gates(8, 1), (true, false, false));
+    assert_eq!(gates(9, 0), (false, false, false));
+    assert_eq!(gates(9, 5), (false, true, false));
+    assert_eq!(gates(11, 0), (false, true, false));
+    assert_eq!(gates(12, 0), (false, true, true));
+
+    assert_eq!(Into::<i32>::into(Where::Unknown(42)), 42);
+    let node = Where::MIPNode {
+        status: 2,
+        objbst: 1.0,
+        objbnd: 0.0,
+        nodcnt: 10.0,
+        solcnt: 1,
+        brvar: None,
+        objbndc: None,
+        openscenarios: None,
+        phase: Some(1),
+        work: Some(0.5),
+        nodleft: Some(3.0),
+    };
+    assert_eq!(Into::<i32>::into(node), MIPNODE);
+}
+
+#[test]
+fn partial_solution_should_be_filled_with_undefined() {
+    let x = Var::new(0);
+    let z = Var::new(2);
+    assert_eq!(
+        solution_buffer(3, &[z.clone(), x.clone()], &[1.0, 0.0]).unwrap(),
+        vec![0.0, UNDEFINED, 1.0]
+    );
+    assert!(solution_buffer(3, &[x.clone()], &[1.0, 2.0]).is_err());
+    assert!(solution_buffer(1, &[x.clone(), z.clone()], &[1.0, 2.0]).is_err());
+    assert!(solution_buffer(2, &[z], &[1.0]).is_err());
+    assert!(solution_buffer(2, &[Var::new(-1)], &[1.0]).is_err());
 }
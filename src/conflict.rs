@@ -0,0 +1,298 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use itertools::Itertools;
+
+use std::ops::Deref;
+
+use super::expr::LinExpr;
+use super::{Constr, Model, ModelSense, Proxy, QConstr, Status, Var, VarType, SOS};
+use crate::error::{Error, Result, DATA_NOT_AVAILABLE};
+use crate::{attr, param, INFINITY};
+
+/// A named group of constraints and variable bounds which are kept or dropped together by
+/// [`ConflictFinder`](struct.ConflictFinder.html)
+#[derive(Debug, Clone)]
+pub struct ConflictGroup {
+    name: String,
+    constrs: Vec<Constr>,
+    qconstrs: Vec<QConstr>,
+    sos: Vec<SOS>,
+    bounds: Vec<Var>,
+}
+
+impl ConflictGroup {
+    /// Create an empty group.
+    pub fn new(name: &str) -> ConflictGroup {
+        ConflictGroup {
+            name: name.to_owned(),
+            constrs: Vec::new(),
+            qconstrs: Vec::new(),
+            sos: Vec::new(),
+            bounds: Vec::new(),
+        }
+    }
+
+    /// Add linear constraints into the group.
+    pub fn constrs(mut self, constrs: &[Constr]) -> Self {
+        self.constrs.extend_from_slice(constrs);
+        self
+    }
+
+    /// Add quadratic constraints into the group.
+    pub fn qconstrs(mut self, qconstrs: &[QConstr]) -> Self {
+        self.qconstrs.extend_from_slice(qconstrs);
+        self
+    }
+
+    /// Add SOS constraints into the group.
+    pub fn sos(mut self, sos: &[SOS]) -> Self {
+        self.sos.extend_from_slice(sos);
+        self
+    }
+
+    /// Add the lower and upper bounds of variables into the group.
+    ///
+    /// When the group is dropped, the variables become free, except that binary variables
+    /// are only relaxed to `[0, 1]`.
+    pub fn bounds(mut self, vars: &[Var]) -> Self {
+        self.bounds.extend_from_slice(vars);
+        self
+    }
+
+    /// Name of the group.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+/// Strategy of the conflict search
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FilterMethod {
+    /// Start from all groups and drop each group whose removal keeps the subsystem infeasible.
+    Deletion,
+    /// Grow the subsystem one group at a time until it becomes infeasible. The result is
+    /// already minimal, and the search is usually faster when the conflict is small.
+    Additive,
+}
+
+/// A minimal set of groups which is infeasible together
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    /// Names of the groups in the conflict
+    pub groups: Vec<String>,
+    /// Number of subproblems solved during the search
+    pub solves: usize,
+}
+
+/// Solver-independent conflict finder based on the deletion and additive filters
+///
+/// Each subproblem is a copy of the model in which the groups outside the candidate set are
+/// removed, with the objective function cleared. Constraints and bounds which do not belong
+/// to any group are always kept.
+/// Unlike [`Model::compute_iis()`](struct.Model.html#method.compute_iis), the conflict is
+/// reported in terms of the user-defined groups.
+///
+/// # Example
+/// ```ignore
+/// let conflict = ConflictFinder::new(FilterMethod::Deletion)
+///     .group(ConflictGroup::new("capacity plant 3").constrs(&capacity[3]))
+///     .group(ConflictGroup::new("demand").constrs(&demand))
+///     .find(&model)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConflictFinder {
+    method: FilterMethod,
+    groups: Vec<ConflictGroup>,
+    time_limit: Option<f64>,
+}
+
+impl ConflictFinder {
+    /// Create a conflict finder with no groups.
+    pub fn new(method: FilterMethod) -> ConflictFinder {
+        ConflictFinder {
+            method,
+            groups: Vec::new(),
+            time_limit: None,
+        }
+    }
+
+    /// Add a group.
+    pub fn group(mut self, group: ConflictGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Set the time limit [sec] of each subproblem.
+    pub fn time_limit(mut self, time_limit: f64) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Search a minimal set of conflicting groups.
+    ///
+    /// Returns `None` if the model is feasible even when all groups are included.
+    pub fn find(&self, model: &Model) -> Result<Option<Conflict>> {
+        let mut solves = 0;
+        let result = {
+            let mut infeasible = |set: &[usize]| {
+                solves += 1;
+                self.is_infeasible(model, set)
+            };
+            let all = (0..self.groups.len()).collect_vec();
+            match self.method {
+                FilterMethod::Deletion => {
+                    if r#try!(infeasible(&all)) {
+                        Some(r#try!(deletion_filter(all, &mut infeasible)))
+                    } else {
+                        None
+                    }
+                }
+                FilterMethod::Additive => r#try!(additive_filter(&all, &mut infeasible)),
+            }
+        };
+
+        Ok(result.map(|set| Conflict {
+            groups: set
+                .into_iter()
+                .map(|i| self.groups[i].name.clone())
+                .collect(),
+            solves,
+        }))
+    }
+
+    // solve the subproblem which includes only the groups in `set`.
+    fn is_infeasible(&self, model: &Model, set: &[usize]) -> Result<bool> {
+        let mut sub = r#try!(model.copy());
+        r#try!(sub.get_env_mut().set(param::OutputFlag, 0));
+        if let Some(time_limit) = self.time_limit {
+            r#try!(sub.get_env_mut().set(param::TimeLimit, time_limit));
+        }
+        r#try!(sub.set_objective(LinExpr::new(), ModelSense::Minimize));
+
+        let vars = sub.vars.clone();
+        let constrs = sub.constrs.clone();
+        let qconstrs = sub.qconstrs.clone();
+        let sos = sub.sos.clone();
+        for (i, group) in self.groups.iter().enumerate() {
+            if set.contains(&i) {
+                continue;
+            }
+            for c in group.constrs.iter() {
+                sub.remove(r#try!(lookup(&constrs, c)));
+            }
+            for q in group.qconstrs.iter() {
+                sub.remove(r#try!(lookup(&qconstrs, q)));
+            }
+            for s in group.sos.iter() {
+                sub.remove(r#try!(lookup(&sos, s)));
+            }
+            for v in group.bounds.iter() {
+                let v = r#try!(lookup(&vars, v));
+                let (lb, ub) = match r#try!(v.vtype(&sub)) {
+                    VarType::Binary => (0.0, 1.0),
+                    _ => (-INFINITY, INFINITY),
+                };
+                r#try!(v.set(&mut sub, attr::LB, lb));
+                r#try!(v.set(&mut sub, attr::UB, ub));
+            }
+        }
+
+        r#try!(sub.optimize());
+        match r#try!(sub.status()) {
+            Status::Infeasible | Status::InfOrUnbd => Ok(true),
+            Status::Optimal | Status::SubOptimal | Status::SolutionLimit => Ok(false),
+            status => Err(Error::FromAPI(
+                format!(
+                    "conflict search subproblem terminated with status {:?}",
+                    status
+                ),
+                DATA_NOT_AVAILABLE,
+            )),
+        }
+    }
+}
+
+// find the element of the copied model which corresponds to `item`.
+fn lookup<P, Q>(items: &[P], item: &Q) -> Result<P>
+where
+    P: Deref<Target = Proxy> + Clone,
+    Q: Deref<Target = Proxy>,
+{
+    let idx = item.index();
+    if idx < 0 || idx as usize >= items.len() {
+        return Err(Error::InconsitentDims);
+    }
+    Ok(items[idx as usize].clone())
+}
+
+// drop each element whose removal keeps the set infeasible.
+fn deletion_filter<F>(mut set: Vec<usize>, infeasible: &mut F) -> Result<Vec<usize>>
+where
+    F: FnMut(&[usize]) -> Result<bool>,
+{
+    let mut pos = 0;
+    while pos < set.len() {
+        let mut candidate = set.clone();
+        candidate.remove(pos);
+        if r#try!(infeasible(&candidate)) {
+            set = candidate;
+        } else {
+            pos += 1;
+        }
+    }
+    Ok(set)
+}
+
+// add elements one at a time until the set becomes infeasible, keeping the last added element.
+// each kept element is necessary for the infeasibility, so the result is minimal.
+fn additive_filter<F>(all: &[usize], infeasible: &mut F) -> Result<Option<Vec<usize>>>
+where
+    F: FnMut(&[usize]) -> Result<bool>,
+{
+    let mut conflict: Vec<usize> = Vec::new();
+    loop {
+        if r#try!(infeasible(&conflict)) {
+            return Ok(Some(conflict));
+        }
+
+        let mut candidate = conflict.clone();
+        let mut found = None;
+        for &i in all.iter().filter(|i| !conflict.contains(i)) {
+            candidate.push(i);
+            if r#try!(infeasible(&candidate)) {
+                found = Some(i);
+                break;
+            }
+        }
+
+        match found {
+            Some(i) => conflict.push(i),
+            None => return Ok(None),
+        }
+    }
+}
+
+#[test]
+fn filters_should_find_minimal_conflict() {
+    // the groups {1, 3} are infeasible together, as is {0, 2, 4}.
+    let mut oracle = |set: &[usize]| -> Result<bool> {
+        Ok((set.contains(&1) && set.contains(&3))
+            || (set.contains(&0) && set.contains(&2) && set.contains(&4)))
+    };
+
+    let all = (0..5).collect_vec();
+    assert_eq!(
+        deletion_filter(all.clone(), &mut oracle).unwrap(),
+        vec![1, 3]
+    );
+
+    let found = additive_filter(&all, &mut oracle).unwrap().unwrap();
+    assert_eq!(found, vec![3, 1]);
+    assert_eq!(deletion_filter(found.clone(), &mut oracle).unwrap(), found);
+
+    let mut feasible = |_: &[usize]| -> Result<bool> { Ok(false) };
+    assert!(additive_filter(&all, &mut feasible).unwrap().is_none());
+}
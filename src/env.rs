@@ -6,11 +6,12 @@
 use crate::ffi;
 
 use std::ffi::CString;
+use std::ops::{Deref, DerefMut};
 use std::ptr::null_mut;
 
 use crate::error::{Error, Result};
 use crate::model::Model;
-use crate::parameter::Param;
+use crate::parameter::{self, Param, ParamSet};
 use crate::util;
 
 /// Gurobi environment object
@@ -91,12 +92,142 @@ impl Env {
     }
 
     /// Set the value of a parameter
+    ///
+    /// Categorical parameters take typed values through
+    /// [`param::typed`](param/typed/index.html), e.g.
+    /// `env.set(param::typed::Method, param::value::Method::Barrier)`.
     pub fn set<P: Param>(&mut self, param: P, value: P::Out) -> Result<()> {
         self.check_apicall(unsafe {
             P::set_param(self.env, param.into().as_ptr(), util::FromRaw::from(value))
         })
     }
 
+    /// Set the values of all parameters in the set
+    pub fn apply(&mut self, params: &ParamSet) -> Result<()> {
+        for (&param, &value) in params.ints() {
+            r#try!(self.set(param, value));
+        }
+        for (&param, &value) in params.doubles() {
+            r#try!(self.set(param, value));
+        }
+        for (&param, value) in params.strings() {
+            r#try!(self.set(param, value.clone()));
+        }
+        Ok(())
+    }
+
+    /// Capture the current values of all parameters.
+    ///
+    /// Parameters which are not supported by the linked Gurobi library are omitted.
+    pub fn snapshot_params(&self) -> ParamSet {
+        let mut params = ParamSet::new();
+        for &param in parameter::INT_PARAMS {
+            if let Ok(value) = self.get(param) {
+                params.insert(param, value);
+            }
+        }
+        for &param in parameter::DOUBLE_PARAMS {
+            if let Ok(value) = self.get(param) {
+                params.insert(param, value);
+            }
+        }
+        for &param in parameter::STRING_PARAMS {
+            if let Ok(value) = self.get(param) {
+                params.insert(param, value);
+            }
+        }
+        params
+    }
+
+    /// Override parameters until the returned guard is dropped.
+    ///
+    /// The previous values of the overridden parameters are restored when the guard is dropped.
+    ///
+    /// # Example
+    /// ```ignore
+    /// {
+    ///     let env = env.with_params(&[(param::TimeLimit, 10.0)][..])?;
+    ///     assert_eq!(env.get(param::TimeLimit)?, 10.0);
+    /// }
+    /// // the previous time limit is restored here.
+    /// ```
+    pub fn with_params<S: Into<ParamSet>>(&mut self, params: S) -> Result<ParamGuard<Env>> {
+        ParamGuard::new(self, params.into())
+    }
+
+    // the current values of the parameters in `keys`.
+    fn current_values(&self, keys: &ParamSet) -> Result<ParamSet> {
+        let mut params = ParamSet::new();
+        for (&param, _) in keys.ints() {
+            params.insert(param, r#try!(self.get(param)));
+        }
+        for (&param, _) in keys.doubles() {
+            params.insert(param, r#try!(self.get(param)));
+        }
+        for (&param, _) in keys.strings() {
+            params.insert(param, r#try!(self.get(param)));
+        }
+        Ok(params)
+    }
+
+    /// Retrieve the default values of all parameters.
+    pub fn default_params(&self) -> ParamSet {
+        let mut params = ParamSet::new();
+        for &param in parameter::INT_PARAMS {
+            let (mut value, mut min, mut max, mut default) = (0, 0, 0, 0);
+            let error = unsafe {
+                ffi::GRBgetintparaminfo(
+                    self.env,
+                    CString::from(param).as_ptr(),
+                    &mut value,
+                    &mut min,
+                    &mut max,
+                    &mut default,
+                )
+            };
+            if error == 0 {
+                params.insert(param, default);
+            }
+        }
+        for &param in parameter::DOUBLE_PARAMS {
+            let (mut value, mut min, mut max, mut default) = (0.0, 0.0, 0.0, 0.0);
+            let error = unsafe {
+                ffi::GRBgetdblparaminfo(
+                    self.env,
+                    CString::from(param).as_ptr(),
+                    &mut value,
+                    &mut min,
+                    &mut max,
+                    &mut default,
+                )
+            };
+            if error == 0 {
+                params.insert(param, default);
+            }
+        }
+        for &param in parameter::STRING_PARAMS {
+            let mut value: Vec<ffi::c_char> = util::Init::init();
+            let mut default: Vec<ffi::c_char> = util::Init::init();
+            let error = unsafe {
+                ffi::GRBgetstrparaminfo(
+                    self.env,
+                    CString::from(param).as_ptr(),
+                    value.as_mut_ptr(),
+                    default.as_mut_ptr(),
+                )
+            };
+            if error == 0 {
+                params.insert(param, util::Into::into(default));
+            }
+        }
+        params
+    }
+
+    /// Retrieve the parameters whose current values are different from their defaults.
+    pub fn non_default_params(&self) -> ParamSet {
+        self.snapshot_params().diff(&self.default_params())
+    }
+
     /// Import a set of parameter values from a file
     pub fn read_params(&mut self, filename: &str) -> Result<()> {
         let filename = r#try!(CString::new(filename));
@@ -118,6 +249,71 @@ impl Env {
     }
 }
 
+/// Objects which own an environment
+pub trait EnvHolder {
+    #[doc(hidden)]
+    fn env_mut(&mut self) -> &mut Env;
+}
+
+impl EnvHolder for Env {
+    fn env_mut(&mut self) -> &mut Env {
+        self
+    }
+}
+
+/// Guard of overridden parameters
+///
+/// Created by [`Env::with_params()`](struct.Env.html#method.with_params) or
+/// [`Model::with_params()`](struct.Model.html#method.with_params). The guard dereferences to the
+/// original object, and restores the previous values of the parameters when dropped.
+pub struct ParamGuard<'a, T: EnvHolder + 'a> {
+    inner: &'a mut T,
+    saved: Option<ParamSet>,
+}
+
+impl<'a, T: EnvHolder + 'a> ParamGuard<'a, T> {
+    #[doc(hidden)]
+    pub fn new(inner: &'a mut T, params: ParamSet) -> Result<ParamGuard<'a, T>> {
+        let saved = r#try!(inner.env_mut().current_values(&params));
+        let guard = ParamGuard {
+            inner,
+            saved: Some(saved),
+        };
+        // if this fails, the values which have been set are restored by `drop()`.
+        r#try!(guard.inner.env_mut().apply(&params));
+        Ok(guard)
+    }
+
+    /// Restore the previous values of the parameters explicitly, and report the error if any.
+    pub fn restore(mut self) -> Result<()> {
+        match self.saved.take() {
+            Some(saved) => self.inner.env_mut().apply(&saved),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, T: EnvHolder + 'a> Deref for ParamGuard<'a, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        self.inner
+    }
+}
+
+impl<'a, T: EnvHolder + 'a> DerefMut for ParamGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.inner
+    }
+}
+
+impl<'a, T: EnvHolder + 'a> Drop for ParamGuard<'a, T> {
+    fn drop(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            let _ = self.inner.env_mut().apply(&saved);
+        }
+    }
+}
+
 pub trait EnvAPI {
     fn get_ptr(&self) -> *mut ffi::GRBenv;
     fn check_apicall(&self, error: ffi::c_int) -> Result<()>;
@@ -199,3 +395,25 @@ fn param_accesors_should_be_valid() {
     let iis_method = env.get(param::IISMethod).unwrap();
     assert_eq!(iis_method, 1);
 }
+
+#[test]
+fn param_guard_should_restore_values() {
+    use super::param;
+    let mut env = Env::new("").unwrap();
+    env.set(param::TimeLimit, 100.0).unwrap();
+    {
+        let overrides = ParamSet::new()
+            .with(param::TimeLimit, 10.0)
+            .with(param::MIPFocus, 2);
+        let guard = env.with_params(&overrides).unwrap();
+        assert_eq!(guard.get(param::TimeLimit).unwrap(), 10.0);
+        assert_eq!(guard.get(param::MIPFocus).unwrap(), 2);
+    }
+    assert_eq!(env.get(param::TimeLimit).unwrap(), 100.0);
+    assert_eq!(env.get(param::MIPFocus).unwrap(), 0);
+
+    let guard = env.with_params(&[(param::Threads, 1)][..]).unwrap();
+    assert_eq!(guard.get(param::Threads).unwrap(), 1);
+    guard.restore().unwrap();
+    assert_eq!(env.get(param::Threads).unwrap(), 0);
+}
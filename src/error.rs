@@ -6,6 +6,10 @@
 use crate::ffi;
 use std;
 
+// error codes of the Gurobi C API, used for the checks performed on the Rust side.
+pub(crate) const DATA_NOT_AVAILABLE: ffi::c_int = 10005;
+pub(crate) const NOT_FOR_MIP: ffi::c_int = 10016;
+
 /// The error type for operations in Gurobi Rust API
 #[derive(Debug)]
 pub enum Error {
@@ -17,6 +21,19 @@ pub enum Error {
 
     /// Inconsistent argument dimensions
     InconsitentDims,
+
+    /// An I/O error while reading or writing a file
+    Io(std::io::Error),
+
+    /// Malformed content of a file parsed by this crate
+    Parse(String),
+
+    /// A raw value which does not correspond to any variant of the expected enum
+    InvalidValue(String),
+
+    /// An error of another type returned from a callback function, which terminated the
+    /// optimization
+    Callback(Box<dyn std::error::Error + Send + Sync>),
 }
 
 impl From<std::ffi::NulError> for Error {
@@ -25,6 +42,12 @@ impl From<std::ffi::NulError> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
@@ -33,6 +56,10 @@ impl std::fmt::Display for Error {
             }
             Error::InconsitentDims => write!(f, "Inconsistent argument dimensions"),
             Error::NulError(ref err) => write!(f, "NulError: {}", err),
+            Error::Io(ref err) => write!(f, "I/O error: {}", err),
+            Error::Parse(ref message) => write!(f, "Parse error: {}", message),
+            Error::InvalidValue(ref message) => write!(f, "Invalid value: {}", message),
+            Error::Callback(ref err) => write!(f, "Error in callback: {}", err),
         }
     }
 }
@@ -43,6 +70,18 @@ impl std::error::Error for Error {
             Error::FromAPI(..) => "error from C API",
             Error::NulError(ref err) => err.description(),
             Error::InconsitentDims => "Inconsistent argument dimensions",
+            Error::Io(ref err) => err.description(),
+            Error::Parse(..) => "Parse error",
+            Error::InvalidValue(..) => "Invalid value",
+            Error::Callback(..) => "error in callback",
+        }
+    }
+
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            Error::Callback(ref err) => Some(err.as_ref()),
+            _ => None,
         }
     }
 }
@@ -51,3 +90,15 @@ impl std::error::Error for Error {
 /// [`Result`](https://doc.rust-lang.org/std/result/enum.Result.html)
 /// type for operations in Gurobi Rust API
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[test]
+fn error_should_be_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    let err = Error::Callback(Box::new(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "user error",
+    )));
+    assert_send_sync(&err);
+    assert_eq!(err.to_string(), "Error in callback: user error");
+    assert!(std::error::Error::source(&err).is_some());
+}
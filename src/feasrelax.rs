@@ -0,0 +1,741 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use crate::ffi;
+use itertools::{Itertools, Zip};
+
+use std::collections::BTreeMap;
+
+use super::expr::{LinExpr, QuadExpr};
+use super::{Constr, ConstrSense, Model, QConstr, RelaxType, Status, Var, VarType};
+use crate::error::{Error, Result};
+use crate::{attr, param, INFINITY};
+
+/// An element of the model which is allowed to be violated in a feasibility relaxation
+#[derive(Debug, Clone)]
+pub enum RelaxedElement {
+    /// A linear constraint
+    Constr(Constr),
+    /// A quadratic constraint.
+    ///
+    /// Relaxed quadratic constraints are replaced by new ones which include the slack
+    /// variables; this is the proxy of the replacement.
+    QConstr(QConstr),
+    /// The lower bound of a variable
+    LowerBound(Var),
+    /// The upper bound of a variable
+    UpperBound(Var),
+}
+
+/// Violation of an original constraint or bound in the solution of a feasibility relaxation
+#[derive(Debug, Clone)]
+pub struct Violation {
+    /// The violated element
+    pub element: RelaxedElement,
+    /// Name of the violated constraint or variable
+    pub name: String,
+    /// Magnitude of the violation
+    pub amount: f64,
+}
+
+/// Result of a feasibility relaxation
+#[derive(Debug, Clone)]
+pub struct RelaxationReport {
+    /// Status of the last optimization
+    pub status: Status,
+    /// Value of the relaxation penalty at the solution, or `None` if no solution is available
+    pub penalty: Option<f64>,
+    /// Violated constraints and bounds, in the order they were given to the builder. Empty if
+    /// no solution is available.
+    pub violations: Vec<Violation>,
+}
+
+/// Builder of a feasibility relaxation
+///
+/// Unlike [`Model::feas_relax()`](struct.Model.html#method.feas_relax), penalties are only
+/// given for the elements which are allowed to be violated. Penalties can be set per
+/// constraint, per group of constraints or by a pattern of constraint names (`*` matches any
+/// sequence of characters and `?` matches a single character). Later settings override
+/// earlier ones, and explicit settings override patterns. A penalty of `INFINITY` means that
+/// the element must not be violated.
+///
+/// The relaxation is built by adding nonnegative slack variables to the model. Infinite bounds
+/// are not relaxed. The bounds of integer, binary and semi-continuous variables are kept; they
+/// are relaxed by a continuous slack which shifts the variable in the linear constraints.
+///
+/// # Example
+/// ```ignore
+/// let report = FeasRelax::new(Linear)
+///     .constr_pattern("capacity_*", 10.0)
+///     .constrs(&demand, 1.0)
+///     .ub(&overtime, 5.0)
+///     .solve(&mut model)?;
+/// for v in report.violations.iter() {
+///     println!("{} is violated by {}", v.name, v.amount);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FeasRelax {
+    relaxtype: RelaxType,
+    minrelax: bool,
+    patterns: Vec<(String, f64)>,
+    constrs: Vec<(Constr, f64)>,
+    qconstrs: Vec<(QConstr, f64)>,
+    lbs: Vec<(Var, f64)>,
+    ubs: Vec<(Var, f64)>,
+}
+
+impl FeasRelax {
+    /// Create a builder of a feasibility relaxation with the given type of penalty.
+    pub fn new(relaxtype: RelaxType) -> FeasRelax {
+        FeasRelax {
+            relaxtype,
+            minrelax: false,
+            patterns: Vec::new(),
+            constrs: Vec::new(),
+            qconstrs: Vec::new(),
+            lbs: Vec::new(),
+            ubs: Vec::new(),
+        }
+    }
+
+    /// If `true`, `solve()` optimizes the original objective among the solutions which
+    /// minimize the penalty. Otherwise, only the penalty is minimized.
+    ///
+    /// The minimum penalty is relaxed by a relative tolerance of `1e-6` in the second
+    /// optimization, so that it stays feasible despite numerical errors.
+    pub fn min_relax(mut self, minrelax: bool) -> Self {
+        self.minrelax = minrelax;
+        self
+    }
+
+    /// Allow a linear constraint to be violated.
+    pub fn constr(mut self, constr: &Constr, penalty: f64) -> Self {
+        self.constrs.push((constr.clone(), penalty));
+        self
+    }
+
+    /// Allow a group of linear constraints to be violated with the same penalty.
+    pub fn constrs(mut self, constrs: &[Constr], penalty: f64) -> Self {
+        self.constrs
+            .extend(constrs.iter().map(|c| (c.clone(), penalty)));
+        self
+    }
+
+    /// Allow the linear constraints whose names match `pattern` to be violated.
+    pub fn constr_pattern(mut self, pattern: &str, penalty: f64) -> Self {
+        self.patterns.push((pattern.to_owned(), penalty));
+        self
+    }
+
+    /// Allow a quadratic constraint to be violated.
+    pub fn qconstr(mut self, qconstr: &QConstr, penalty: f64) -> Self {
+        self.qconstrs.push((qconstr.clone(), penalty));
+        self
+    }
+
+    /// Allow a group of quadratic constraints to be violated with the same penalty.
+    pub fn qconstrs(mut self, qconstrs: &[QConstr], penalty: f64) -> Self {
+        self.qconstrs
+            .extend(qconstrs.iter().map(|q| (q.clone(), penalty)));
+        self
+    }
+
+    /// Allow the lower bound of a variable to be violated.
+    pub fn lb(mut self, var: &Var, penalty: f64) -> Self {
+        self.lbs.push((var.clone(), penalty));
+        self
+    }
+
+    /// Allow the upper bound of a variable to be violated.
+    pub fn ub(mut self, var: &Var, penalty: f64) -> Self {
+        self.ubs.push((var.clone(), penalty));
+        self
+    }
+
+    /// Allow both bounds of variables to be violated with the same penalty.
+    pub fn bounds(mut self, vars: &[Var], penalty: f64) -> Self {
+        for v in vars.iter() {
+            self.lbs.push((v.clone(), penalty));
+            self.ubs.push((v.clone(), penalty));
+        }
+        self
+    }
+
+    /// Modify the model into the feasibility relaxation and optimize it.
+    ///
+    /// This method will modify the model. If you don't want to modify the model, copy the model
+    /// before invoking this method (see also [`Model::copy()`](struct.Model.html#method.copy)).
+    pub fn solve(&self, model: &mut Model) -> Result<RelaxationReport> {
+        let relaxation = r#try!(self.apply(model));
+        r#try!(model.optimize());
+
+        if self.minrelax && r#try!(model.status()) == Status::Optimal {
+            let penalty = r#try!(model.get(attr::ObjVal));
+            r#try!(relaxation.restore_objective(model, max_penalty(penalty)));
+            r#try!(model.optimize());
+        }
+
+        relaxation.report(model)
+    }
+
+    /// Modify the model into the feasibility relaxation without optimizing it.
+    ///
+    /// The objective function of the model is replaced by the relaxation penalty.
+    pub fn apply(&self, model: &mut Model) -> Result<Relaxation> {
+        r#try!(model.update());
+
+        // resolve penalties for linear constraints.
+        let mut constr_pen = BTreeMap::new();
+        if !self.patterns.is_empty() {
+            let names = r#try!(model.get_values(attr::ConstrName, model.constrs.as_slice()));
+            for &(ref pattern, pen) in self.patterns.iter() {
+                for (c, name) in Zip::new((model.constrs.iter(), names.iter())) {
+                    if glob_match(pattern.as_bytes(), name.as_bytes()) {
+                        constr_pen.insert(c.index(), (c.clone(), pen));
+                    }
+                }
+            }
+        }
+        for &(ref c, pen) in self.constrs.iter() {
+            if c.index() < 0 {
+                return Err(Error::InconsitentDims);
+            }
+            constr_pen.insert(c.index(), (c.clone(), pen));
+        }
+
+        let mut qconstr_pen = BTreeMap::new();
+        for &(ref q, pen) in self.qconstrs.iter() {
+            if q.index() < 0 {
+                return Err(Error::InconsitentDims);
+            }
+            qconstr_pen.insert(q.index(), (q.clone(), pen));
+        }
+
+        let mut lb_pen = BTreeMap::new();
+        for &(ref v, pen) in self.lbs.iter() {
+            lb_pen.insert(v.index(), (v.clone(), pen));
+        }
+        let mut ub_pen = BTreeMap::new();
+        for &(ref v, pen) in self.ubs.iter() {
+            ub_pen.insert(v.index(), (v.clone(), pen));
+        }
+
+        let objective = r#try!(Objective::save(model));
+        let mut entries = Vec::new();
+
+        // linear constraints: add slack columns directly into the rows.
+        for (_, (c, pen)) in constr_pen.into_iter() {
+            if pen >= INFINITY {
+                continue;
+            }
+            let name = r#try!(c.get(model, attr::ConstrName));
+            let sense = r#try!(c.sense(model));
+            let mut slacks = Vec::new();
+            for &(coeff, suffix) in slack_coeffs(sense).iter() {
+                let s = r#try!(model.add_var(
+                    &format!("{}_{}", name, suffix),
+                    VarType::Continuous,
+                    0.0,
+                    0.0,
+                    INFINITY,
+                    std::slice::from_ref(&c),
+                    &[coeff]
+                ));
+                slacks.push(s);
+            }
+            entries.push(Entry {
+                element: RelaxedElement::Constr(c),
+                name,
+                penalty: pen,
+                slacks,
+            });
+        }
+
+        // variable bounds and quadratic constraints: add slack variables first.
+        let mut bound_slacks = Vec::new();
+        for (lower, pens) in vec![(true, lb_pen), (false, ub_pen)].into_iter() {
+            for (_, (v, pen)) in pens.into_iter() {
+                if pen >= INFINITY {
+                    continue;
+                }
+                let bound = if lower {
+                    r#try!(v.get(model, attr::LB))
+                } else {
+                    r#try!(v.get(model, attr::UB))
+                };
+                if bound <= -INFINITY || bound >= INFINITY {
+                    continue;
+                }
+                let name = r#try!(v.get(model, attr::VarName));
+                let slack_name = format!("{}_{}_slack", name, if lower { "lb" } else { "ub" });
+                if r#try!(v.vtype(model)) == VarType::Continuous {
+                    let s = r#try!(model.add_var(
+                        &slack_name,
+                        VarType::Continuous,
+                        0.0,
+                        0.0,
+                        INFINITY,
+                        &[],
+                        &[]
+                    ));
+                    bound_slacks.push((lower, v, bound, name, pen, s));
+                    continue;
+                }
+
+                // the variable is replaced by `v - s` (or `v + s`) in its linear constraints.
+                let sign = if lower { -1.0 } else { 1.0 };
+                let (rows, coeffs): (Vec<_>, Vec<_>) = r#try!(model.get_var_terms(&v))
+                    .into_iter()
+                    .map(|(i, coeff)| (model.constrs[i as usize].clone(), sign * coeff))
+                    .unzip();
+                let s = r#try!(model.add_var(
+                    &slack_name,
+                    VarType::Continuous,
+                    0.0,
+                    0.0,
+                    INFINITY,
+                    &rows,
+                    &coeffs
+                ));
+                entries.push(Entry {
+                    element: if lower {
+                        RelaxedElement::LowerBound(v)
+                    } else {
+                        RelaxedElement::UpperBound(v)
+                    },
+                    name,
+                    penalty: pen,
+                    slacks: vec![s],
+                });
+            }
+        }
+
+        let mut qconstr_slacks = Vec::new();
+        for (_, (q, pen)) in qconstr_pen.into_iter() {
+            if pen >= INFINITY {
+                continue;
+            }
+            let name = r#try!(q.get(model, attr::QCName));
+            let sense = r#try!(q.sense(model));
+            let mut slacks = Vec::new();
+            for &(coeff, suffix) in slack_coeffs(sense).iter() {
+                let s = r#try!(model.add_var(
+                    &format!("{}_{}", name, suffix),
+                    VarType::Continuous,
+                    0.0,
+                    0.0,
+                    INFINITY,
+                    &[],
+                    &[]
+                ));
+                slacks.push((coeff, s));
+            }
+            qconstr_slacks.push((q, name, sense, pen, slacks));
+        }
+        r#try!(model.update());
+
+        for (lower, v, bound, name, pen, s) in bound_slacks.into_iter() {
+            if lower {
+                r#try!(model.add_constr(
+                    &format!("{}_lb", name),
+                    &v + &s,
+                    ConstrSense::Greater,
+                    bound
+                ));
+                r#try!(v.set(model, attr::LB, -INFINITY));
+            } else {
+                r#try!(model.add_constr(
+                    &format!("{}_ub", name),
+                    &v - &s,
+                    ConstrSense::Less,
+                    bound
+                ));
+                r#try!(v.set(model, attr::UB, INFINITY));
+            }
+            entries.push(Entry {
+                element: if lower {
+                    RelaxedElement::LowerBound(v)
+                } else {
+                    RelaxedElement::UpperBound(v)
+                },
+                name,
+                penalty: pen,
+                slacks: vec![s],
+            });
+        }
+
+        for (q, name, sense, pen, slacks) in qconstr_slacks.into_iter() {
+            let rhs = r#try!(q.get(model, attr::QCRHS));
+            let (lterms, qterms) = r#try!(model.get_qconstr_terms(&q));
+
+            let mut expr = QuadExpr::new();
+            for (j, coeff) in lterms.into_iter() {
+                expr = expr.add_term(coeff, model.vars[j as usize].clone());
+            }
+            for (i, j, coeff) in qterms.into_iter() {
+                expr = expr.add_qterm(
+                    coeff,
+                    model.vars[i as usize].clone(),
+                    model.vars[j as usize].clone(),
+                );
+            }
+
+            for &(coeff, ref s) in slacks.iter() {
+                expr = expr.add_term(coeff, s.clone());
+            }
+
+            model.remove(q);
+            let replaced = r#try!(model.add_qconstr(&name, expr, sense, rhs));
+            r#try!(model.update());
+            entries.push(Entry {
+                element: RelaxedElement::QConstr(replaced),
+                name,
+                penalty: pen,
+                slacks: slacks.into_iter().map(|(_, s)| s).collect(),
+            });
+        }
+
+        let mut relaxation = Relaxation {
+            relaxtype: self.relaxtype,
+            objective,
+            entries,
+            indicators: Vec::new(),
+        };
+        r#try!(relaxation.set_penalty_objective(model));
+        Ok(relaxation)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    element: RelaxedElement,
+    name: String,
+    penalty: f64,
+    slacks: Vec<Var>,
+}
+
+// the objective function of the original model.
+#[derive(Debug, Clone)]
+struct Objective {
+    vars: Vec<Var>,
+    obj: Vec<f64>,
+    objcon: f64,
+    sense: i32,
+    qterms: Vec<(i32, i32, f64)>,
+}
+
+impl Objective {
+    fn save(model: &Model) -> Result<Objective> {
+        let vars = model.vars.clone();
+        let obj = r#try!(model.get_values(attr::Obj, vars.as_slice()));
+        let objcon = r#try!(model.get(attr::ObjCon));
+        let sense = r#try!(model.get(attr::ModelSense));
+
+        let mut numqnz = r#try!(model.get(attr::NumQNZs));
+        let mut qrow = vec![0; numqnz as usize];
+        let mut qcol = vec![0; numqnz as usize];
+        let mut qval = vec![0.0; numqnz as usize];
+        if numqnz > 0 {
+            r#try!(model.check_apicall(unsafe {
+                ffi::GRBgetq(
+                    model.model,
+                    &mut numqnz,
+                    qrow.as_mut_ptr(),
+                    qcol.as_mut_ptr(),
+                    qval.as_mut_ptr(),
+                )
+            }));
+        }
+
+        Ok(Objective {
+            vars,
+            obj,
+            objcon,
+            sense,
+            qterms: Zip::new((qrow, qcol, qval)).collect(),
+        })
+    }
+}
+
+/// A feasibility relaxation applied to a model
+///
+/// Created by [`FeasRelax::apply()`](struct.FeasRelax.html#method.apply).
+#[derive(Debug, Clone)]
+pub struct Relaxation {
+    relaxtype: RelaxType,
+    objective: Objective,
+    entries: Vec<Entry>,
+    indicators: Vec<Var>,
+}
+
+impl Relaxation {
+    /// Retrieve the slack variables added for each relaxed element.
+    pub fn slacks(&self) -> Vec<(&RelaxedElement, &[Var])> {
+        self.entries
+            .iter()
+            .map(|e| (&e.element, e.slacks.as_slice()))
+            .collect()
+    }
+
+    /// Compute the violations of the original constraints and bounds from the current solution.
+    pub fn report(&self, model: &Model) -> Result<RelaxationReport> {
+        let status = r#try!(model.status());
+        if !status.has_solution() && r#try!(model.get(attr::SolCount)) == 0 {
+            return Ok(RelaxationReport {
+                status,
+                penalty: None,
+                violations: Vec::new(),
+            });
+        }
+
+        let mut values = Vec::with_capacity(self.entries.len());
+        for e in self.entries.iter() {
+            values.push(r#try!(model.get_values(attr::X, e.slacks.as_slice())));
+        }
+        Ok(self.assemble(status, &values))
+    }
+
+    // build the report from the values of the slack variables of each entry.
+    fn assemble(&self, status: Status, values: &[Vec<f64>]) -> RelaxationReport {
+        let mut penalty = 0.0;
+        let mut violations = Vec::new();
+        for (e, values) in self.entries.iter().zip(values) {
+            for &x in values.iter() {
+                penalty += match self.relaxtype {
+                    RelaxType::Linear => e.penalty * x,
+                    RelaxType::Quadratic => e.penalty * x * x,
+                    RelaxType::Cardinality => {
+                        if x > 1e-9 {
+                            e.penalty
+                        } else {
+                            0.0
+                        }
+                    }
+                };
+            }
+            let amount: f64 = values.iter().sum();
+            if amount > 1e-9 {
+                violations.push(Violation {
+                    element: e.element.clone(),
+                    name: e.name.clone(),
+                    amount,
+                });
+            }
+        }
+
+        RelaxationReport {
+            status,
+            penalty: Some(penalty),
+            violations,
+        }
+    }
+
+    /// Restore the original objective function and restrict the relaxation penalty to at most
+    /// `max_penalty`.
+    pub fn restore_objective(&self, model: &mut Model, max_penalty: f64) -> Result<()> {
+        // bound the penalty.
+        match self.relaxtype {
+            RelaxType::Quadratic => {
+                let mut expr = QuadExpr::new();
+                for e in self.entries.iter() {
+                    for s in e.slacks.iter() {
+                        expr = expr.add_qterm(e.penalty, s.clone(), s.clone());
+                    }
+                }
+                r#try!(model.add_qconstr(
+                    "feasrelax_penalty",
+                    expr,
+                    ConstrSense::Less,
+                    max_penalty
+                ));
+            }
+            RelaxType::Linear | RelaxType::Cardinality => {
+                let (mut vars, coeffs) = self.penalty_terms();
+                if let RelaxType::Cardinality = self.relaxtype {
+                    vars = self.indicators.clone();
+                }
+                let expr = LinExpr::new().add_terms(coeffs.as_slice(), vars.as_slice());
+                r#try!(model.add_constr("feasrelax_penalty", expr, ConstrSense::Less, max_penalty));
+            }
+        }
+        r#try!(model.update());
+
+        // restore the original objective.
+        let all = model.vars.clone();
+        r#try!(model.set_values(attr::Obj, all.as_slice(), vec![0.0; all.len()].as_slice()));
+        r#try!(model.del_qpterms());
+        let obj = &self.objective;
+        r#try!(model.set_values(attr::Obj, obj.vars.as_slice(), obj.obj.as_slice()));
+        if !obj.qterms.is_empty() {
+            let qrow = obj.qterms.iter().map(|t| t.0).collect_vec();
+            let qcol = obj.qterms.iter().map(|t| t.1).collect_vec();
+            let qval = obj.qterms.iter().map(|t| t.2).collect_vec();
+            r#try!(model.add_qpterms(qrow.as_slice(), qcol.as_slice(), qval.as_slice()));
+        }
+        r#try!(model.set(attr::ObjCon, obj.objcon));
+        model.set(attr::ModelSense, obj.sense)
+    }
+
+    // slack variables and their penalties.
+    fn penalty_terms(&self) -> (Vec<Var>, Vec<f64>) {
+        let (mut vars, mut coeffs) = (Vec::new(), Vec::new());
+        for e in self.entries.iter() {
+            for s in e.slacks.iter() {
+                vars.push(s.clone());
+                coeffs.push(e.penalty);
+            }
+        }
+        (vars, coeffs)
+    }
+
+    fn set_penalty_objective(&mut self, model: &mut Model) -> Result<()> {
+        let all = model.vars.clone();
+        r#try!(model.set_values(attr::Obj, all.as_slice(), vec![0.0; all.len()].as_slice()));
+        r#try!(model.del_qpterms());
+        r#try!(model.set(attr::ObjCon, 0.0));
+        r#try!(model.set(attr::ModelSense, 1));
+
+        match self.relaxtype {
+            RelaxType::Linear => {
+                let (vars, coeffs) = self.penalty_terms();
+                model.set_values(attr::Obj, vars.as_slice(), coeffs.as_slice())
+            }
+            RelaxType::Quadratic => {
+                let mut qrow = Vec::new();
+                let mut qval = Vec::new();
+                for e in self.entries.iter() {
+                    for s in e.slacks.iter() {
+                        qrow.push(s.index());
+                        qval.push(e.penalty);
+                    }
+                }
+                model.add_qpterms(qrow.as_slice(), qrow.as_slice(), qval.as_slice())
+            }
+            RelaxType::Cardinality => {
+                // each slack is linked to a binary indicator by a big-M constraint.
+                let bigm = r#try!(model.get_env().get(param::FeasRelaxBigM));
+                let (vars, coeffs) = self.penalty_terms();
+                let mut indicators = Vec::with_capacity(vars.len());
+                for (s, &pen) in Zip::new((vars.iter(), coeffs.iter())) {
+                    let name = r#try!(s.get(model, attr::VarName));
+                    let z = r#try!(model.add_var(
+                        &format!("{}_ind", name),
+                        VarType::Binary,
+                        pen,
+                        0.0,
+                        1.0,
+                        &[],
+                        &[]
+                    ));
+                    indicators.push((s.clone(), z));
+                }
+                r#try!(model.update());
+                for (s, z) in indicators.into_iter() {
+                    self.indicators.push(z.clone());
+                    let name = r#try!(s.get(model, attr::VarName));
+                    r#try!(model.add_constr(
+                        &format!("{}_bigm", name),
+                        &s - bigm * &z,
+                        ConstrSense::Less,
+                        0.0
+                    ));
+                }
+                model.update()
+            }
+        }
+    }
+}
+
+// coefficients and name suffixes of the slack variables of a constraint; `Equal` rows are
+// relaxed in both directions.
+fn slack_coeffs(sense: ConstrSense) -> &'static [(f64, &'static str)] {
+    match sense {
+        ConstrSense::Less => &[(-1.0, "slack")],
+        ConstrSense::Greater => &[(1.0, "slack")],
+        ConstrSense::Equal => &[(1.0, "slack_pos"), (-1.0, "slack_neg")],
+    }
+}
+
+// upper bound of the penalty in the second phase of `min_relax`.
+fn max_penalty(penalty: f64) -> f64 {
+    penalty + 1e-6 * penalty.abs().max(1.0)
+}
+
+// match a name against a pattern with wildcards `*` and `?`.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some(&b'*'), _) => {
+            glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..]))
+        }
+        (Some(&b'?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+#[test]
+fn glob_pattern_should_match_names() {
+    assert!(glob_match(b"capacity_*", b"capacity_plant3"));
+    assert!(glob_match(b"c?_*", b"c1_x"));
+    assert!(glob_match(b"*", b""));
+    assert!(!glob_match(b"capacity_*", b"demand_1"));
+    assert!(!glob_match(b"c?", b"c10"));
+}
+
+#[test]
+fn relaxation_report_should_be_assembled_from_slacks() {
+    let c = Constr::new(0);
+    let x = Var::new(0);
+    assert_eq!(slack_coeffs(ConstrSense::Less), &[(-1.0, "slack")]);
+    assert_eq!(
+        slack_coeffs(ConstrSense::Equal),
+        &[(1.0, "slack_pos"), (-1.0, "slack_neg")]
+    );
+    assert_eq!(max_penalty(0.0), 1e-6);
+    assert_eq!(max_penalty(-2e6), -2e6 + 2.0);
+
+    let entry = |element, name: &str, penalty, n| Entry {
+        element,
+        name: name.to_owned(),
+        penalty,
+        slacks: (0..n).map(|i| Var::new(i + 1)).collect(),
+    };
+    let mut relaxation = Relaxation {
+        relaxtype: RelaxType::Linear,
+        objective: Objective {
+            vars: Vec::new(),
+            obj: Vec::new(),
+            objcon: 0.0,
+            sense: 1,
+            qterms: Vec::new(),
+        },
+        entries: vec![
+            entry(RelaxedElement::Constr(c.clone()), "c", 2.0, 2),
+            entry(RelaxedElement::UpperBound(x.clone()), "x", 1.0, 1),
+        ],
+        indicators: Vec::new(),
+    };
+    assert_eq!(relaxation.slacks()[0].1.len(), 2);
+
+    let values = vec![vec![0.0, 1.5], vec![0.0]];
+    let report = relaxation.assemble(Status::Optimal, &values);
+    assert_eq!(report.penalty, Some(3.0));
+    assert_eq!(report.violations.len(), 1);
+    assert_eq!(report.violations[0].name, "c");
+    assert_eq!(report.violations[0].amount, 1.5);
+
+    relaxation.relaxtype = RelaxType::Quadratic;
+    assert_eq!(
+        relaxation.assemble(Status::Optimal, &values).penalty,
+        Some(4.5)
+    );
+    relaxation.relaxtype = RelaxType::Cardinality;
+    assert_eq!(
+        relaxation.assemble(Status::Optimal, &values).penalty,
+        Some(2.0)
+    );
+}
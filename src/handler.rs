@@ -0,0 +1,327 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use super::callback::{Callback, Context, Where};
+use super::callback::{MessageCtx, MipNodeCtx, MipSolCtx, MultiObjCtx, ProgressCtx};
+use super::Model;
+use crate::error::Result;
+
+/// Handler of the callback, with a method for each location
+///
+/// All methods do nothing by default, so a handler only implements the locations it is
+/// interested in. Returning an error terminates the optimization (see
+/// [`Model::optimize_with_callback()`](struct.Model.html#method.optimize_with_callback)).
+///
+/// # Example
+/// ```ignore
+/// struct Logger;
+///
+/// impl CallbackHandler for Logger {
+///     fn on_message(&mut self, ctx: &MessageCtx) -> Result<()> {
+///         print!("{}", ctx.message());
+///         Ok(())
+///     }
+/// }
+///
+/// model.optimize_with_handler(&mut Logger)?;
+/// ```
+#[allow(unused_variables)]
+pub trait CallbackHandler {
+    /// Called at `Polling`.
+    fn on_polling(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `PreSolve`.
+    fn on_presolve(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `Simplex`.
+    fn on_simplex(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `MIP`.
+    fn on_mip(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `MIPSol`.
+    fn on_mip_sol(&mut self, ctx: &MipSolCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `MIPNode`.
+    fn on_mip_node(&mut self, ctx: &MipNodeCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `Message`.
+    fn on_message(&mut self, ctx: &MessageCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `Barrier`.
+    fn on_barrier(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `MultiObj`.
+    fn on_multiobj(&mut self, ctx: &MultiObjCtx) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called at `IIS`.
+    fn on_iis(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+macro_rules! forward_handler {
+    ($($method:ident($ctx:ty);)*) => {
+        impl<'a, H: CallbackHandler + ?Sized> CallbackHandler for &'a mut H {
+            $(
+                fn $method(&mut self, ctx: &$ctx) -> Result<()> {
+                    (**self).$method(ctx)
+                }
+            )*
+        }
+
+        impl<H: CallbackHandler + ?Sized> CallbackHandler for Box<H> {
+            $(
+                fn $method(&mut self, ctx: &$ctx) -> Result<()> {
+                    (**self).$method(ctx)
+                }
+            )*
+        }
+
+        impl<'a> CallbackHandler for CallbackChain<'a> {
+            $(
+                fn $method(&mut self, ctx: &$ctx) -> Result<()> {
+                    for handler in self.handlers.iter_mut() {
+                        r#try!(handler.$method(ctx));
+                    }
+                    Ok(())
+                }
+            )*
+        }
+    };
+}
+
+/// Sequence of callback handlers, which are called in order at each location
+///
+/// The chain stops at the first handler which returns an error.
+///
+/// # Example
+/// ```ignore
+/// let mut chain = CallbackChain::new()
+///     .with(separator)
+///     .with(&mut logger)
+///     .with(policy);
+/// model.optimize_with_handler(&mut chain)?;
+/// ```
+#[derive(Default)]
+pub struct CallbackChain<'a> {
+    handlers: Vec<Box<dyn CallbackHandler + 'a>>,
+}
+
+impl<'a> CallbackChain<'a> {
+    /// Create an empty chain.
+    pub fn new() -> CallbackChain<'a> {
+        CallbackChain::default()
+    }
+
+    /// Append a handler to the chain.
+    pub fn with<H: CallbackHandler + 'a>(mut self, handler: H) -> Self {
+        self.push(handler);
+        self
+    }
+
+    /// Append a handler to the chain.
+    pub fn push<H: CallbackHandler + 'a>(&mut self, handler: H) {
+        self.handlers.push(Box::new(handler));
+    }
+
+    /// Number of the handlers in the chain.
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Returns `true` if the chain has no handlers.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
+
+forward_handler! {
+    on_polling(ProgressCtx);
+    on_presolve(ProgressCtx);
+    on_simplex(ProgressCtx);
+    on_mip(ProgressCtx);
+    on_mip_sol(MipSolCtx);
+    on_mip_node(MipNodeCtx);
+    on_message(MessageCtx);
+    on_barrier(ProgressCtx);
+    on_multiobj(MultiObjCtx);
+    on_iis(ProgressCtx);
+}
+
+// call the method of the handler which corresponds to the location of the callback.
+fn dispatch<H: CallbackHandler + ?Sized>(handler: &mut H, cb: &Callback) -> Result<()> {
+    match cb.context() {
+        Context::MIPSol(ref ctx) => handler.on_mip_sol(ctx),
+        Context::MIPNode(ref ctx) => handler.on_mip_node(ctx),
+        Context::Message(ref ctx) => handler.on_message(ctx),
+        Context::MultiObj(ref ctx) => handler.on_multiobj(ctx),
+        Context::Progress(ref ctx) => match *ctx.info() {
+            Where::Polling => handler.on_polling(ctx),
+            Where::PreSolve { .. } => handler.on_presolve(ctx),
+            Where::Simplex { .. } => handler.on_simplex(ctx),
+            Where::MIP { .. } => handler.on_mip(ctx),
+            Where::Barrier { .. } => handler.on_barrier(ctx),
+            Where::IIS { .. } => handler.on_iis(ctx),
+            _ => Ok(()),
+        },
+    }
+}
+
+impl Model {
+    /// Optimize the model with a callback handler.
+    ///
+    /// See [`optimize_with_callback()`](#method.optimize_with_callback) for the treatment of
+    /// errors and panics.
+    pub fn optimize_with_handler<H: CallbackHandler + ?Sized>(
+        &mut self,
+        handler: &mut H,
+    ) -> Result<()> {
+        self.optimize_with_callback(|cb| dispatch(handler, &cb))
+    }
+}
+
+#[cfg(test)]
+use super::callback::with_detached_callback;
+#[cfg(test)]
+use crate::error::Error;
+#[cfg(test)]
+use std::cell::RefCell;
+
+#[cfg(test)]
+struct Recorder<'a> {
+    id: usize,
+    fail: bool,
+    log: &'a RefCell<Vec<(usize, &'static str)>>,
+}
+
+#[cfg(test)]
+impl<'a> Recorder<'a> {
+    fn record(&self, location: &'static str) -> Result<()> {
+        self.log.borrow_mut().push((self.id, location));
+        if self.fail {
+            Err(Error::InvalidValue(format!("handler {}", self.id)))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+impl<'a> CallbackHandler for Recorder<'a> {
+    fn on_polling(&mut self, _: &ProgressCtx) -> Result<()> {
+        self.record("polling")
+    }
+
+    fn on_simplex(&mut self, _: &ProgressCtx) -> Result<()> {
+        self.record("simplex")
+    }
+}
+
+#[cfg(test)]
+fn simplex() -> Where {
+    Where::Simplex {
+        itrcnt: 0.0,
+        objval: 0.0,
+        priminf: 0.0,
+        dualinf: 0.0,
+        ispert: 0,
+    }
+}
+
+#[test]
+fn chain_should_call_handlers_in_order() {
+    let log = RefCell::new(Vec::new());
+    let recorder = |id| Recorder {
+        id,
+        fail: false,
+        log: &log,
+    };
+    let mut second = recorder(1);
+    let mut chain = CallbackChain::new()
+        .with(recorder(0))
+        .with(&mut second)
+        .with(Box::new(recorder(2)));
+    assert_eq!(chain.len(), 3);
+
+    with_detached_callback(Where::Polling, |cb| dispatch(&mut chain, cb)).unwrap();
+    with_detached_callback(simplex(), |cb| dispatch(&mut chain, cb)).unwrap();
+    let iis = Where::IIS {
+        constrmin: 0,
+        constrmax: 0,
+        constrguess: 0,
+        boundmin: 0,
+        boundmax: 0,
+        boundguess: 0,
+    };
+    with_detached_callback(iis, |cb| dispatch(&mut chain, cb)).unwrap();
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            (0, "polling"),
+            (1, "polling"),
+            (2, "polling"),
+            (0, "simplex"),
+            (1, "simplex"),
+            (2, "simplex"),
+        ]
+    );
+}
+
+#[test]
+fn chain_should_stop_at_first_error() {
+    let log = RefCell::new(Vec::new());
+    let mut chain = CallbackChain::new();
+    for id in 0..3 {
+        chain.push(Recorder {
+            id,
+            fail: id == 1,
+            log: &log,
+        });
+    }
+
+    match with_detached_callback(Where::Polling, |cb| dispatch(&mut chain, cb)) {
+        Err(Error::InvalidValue(msg)) => assert_eq!(msg, "handler 1"),
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert_eq!(*log.borrow(), vec![(0, "polling"), (1, "polling")]);
+}
+
+#[test]
+fn references_and_boxes_should_forward_to_handler() {
+    let log = RefCell::new(Vec::new());
+    let mut recorder = Recorder {
+        id: 0,
+        fail: false,
+        log: &log,
+    };
+    with_detached_callback(Where::Polling, |cb| dispatch(&mut &mut recorder, cb)).unwrap();
+
+    let mut boxed: Box<dyn CallbackHandler> = Box::new(Recorder {
+        id: 1,
+        fail: false,
+        log: &log,
+    });
+    with_detached_callback(simplex(), |cb| dispatch(&mut boxed, cb)).unwrap();
+    assert_eq!(*log.borrow(), vec![(0, "polling"), (1, "simplex")]);
+}
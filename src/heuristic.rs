@@ -0,0 +1,231 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use itertools::Itertools;
+
+use super::callback::{MipNodeCtx, ModelView};
+use super::handler::CallbackHandler;
+use super::separation::SolutionView;
+use super::{Status, Var};
+use crate::error::{Error, Result};
+
+/// Values of a subset of the variables, to be completed by Gurobi
+#[derive(Debug, Clone, Default)]
+pub struct PartialSolution {
+    vars: Vec<Var>,
+    values: Vec<f64>,
+}
+
+impl PartialSolution {
+    /// Create an empty solution.
+    pub fn new() -> PartialSolution {
+        PartialSolution::default()
+    }
+
+    /// Set the value of a variable.
+    pub fn set(mut self, var: &Var, value: f64) -> Self {
+        self.push(var, value);
+        self
+    }
+
+    /// Set the value of a variable.
+    pub fn push(&mut self, var: &Var, value: f64) {
+        self.vars.push(var.clone());
+        self.values.push(value);
+    }
+
+    /// Set the values of variables.
+    pub fn extend(mut self, vars: &[Var], values: &[f64]) -> Result<Self> {
+        if vars.len() != values.len() {
+            return Err(Error::InconsitentDims);
+        }
+        self.vars.extend_from_slice(vars);
+        self.values.extend_from_slice(values);
+        Ok(self)
+    }
+
+    /// Number of the variables with values.
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Returns `true` if no values are given.
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+}
+
+/// Heuristic which proposes solutions from the relaxation at MIP nodes
+///
+/// # Example
+/// ```ignore
+/// let rounding = |_: ModelView, relaxation: &SolutionView| {
+///     let mut sol = PartialSolution::new();
+///     for v in &binaries {
+///         match relaxation.value(v) {
+///             Some(x) if x < 0.1 || x > 0.9 => sol.push(v, x.round()),
+///             _ => {}
+///         }
+///     }
+///     Ok(Some(sol))
+/// };
+///
+/// let mut heuristic = Heuristic::new(rounding);
+/// model.optimize_with_handler(&mut heuristic)?;
+/// println!("{} of {} solutions accepted", heuristic.accepted(), heuristic.proposed());
+/// ```
+pub trait PrimalHeuristic {
+    /// Propose a (partial) solution from the optimal solution of the node relaxation.
+    ///
+    /// Returning `None` skips the node.
+    fn propose(
+        &mut self,
+        model: ModelView,
+        relaxation: &SolutionView,
+    ) -> Result<Option<PartialSolution>>;
+}
+
+impl<F> PrimalHeuristic for F
+where
+    F: FnMut(ModelView, &SolutionView) -> Result<Option<PartialSolution>>,
+{
+    fn propose(
+        &mut self,
+        model: ModelView,
+        relaxation: &SolutionView,
+    ) -> Result<Option<PartialSolution>> {
+        self(model, relaxation)
+    }
+}
+
+/// Driver which calls a primal heuristic at MIP nodes
+///
+/// The driver is a [`CallbackHandler`](trait.CallbackHandler.html). The acceptance of the
+/// proposed solutions is only reported since Gurobi 8.0.
+pub struct Heuristic<H: PrimalHeuristic> {
+    heuristic: H,
+    frequency: usize,
+    nodes: usize,
+    proposed: usize,
+    accepted: usize,
+    last_objective: Option<f64>,
+}
+
+impl<H: PrimalHeuristic> Heuristic<H> {
+    /// Create a driver which calls the heuristic at every node.
+    pub fn new(heuristic: H) -> Heuristic<H> {
+        Heuristic {
+            heuristic,
+            frequency: 1,
+            nodes: 0,
+            proposed: 0,
+            accepted: 0,
+            last_objective: None,
+        }
+    }
+
+    /// Call the heuristic only at every `frequency`-th node with an optimal relaxation.
+    ///
+    /// A frequency of `0` is treated as `1`.
+    pub fn frequency(mut self, frequency: usize) -> Self {
+        self.frequency = frequency.max(1);
+        self
+    }
+
+    /// Retrieve the heuristic.
+    pub fn heuristic(&self) -> &H {
+        &self.heuristic
+    }
+
+    /// Number of the solutions proposed to Gurobi.
+    pub fn proposed(&self) -> usize {
+        self.proposed
+    }
+
+    /// Number of the proposed solutions from which Gurobi found a new solution.
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// Objective value of the last solution found from the proposals.
+    pub fn last_objective(&self) -> Option<f64> {
+        self.last_objective
+    }
+
+    /// Consume the driver and retrieve the heuristic.
+    pub fn into_inner(self) -> H {
+        self.heuristic
+    }
+
+    // count a node with an optimal relaxation, and returns `true` if the heuristic is called.
+    fn next_node(&mut self) -> bool {
+        self.nodes += 1;
+        (self.nodes - 1).is_multiple_of(self.frequency)
+    }
+
+    // record the result of a proposal.
+    fn record(&mut self, objval: Option<f64>) {
+        self.proposed += 1;
+        if let Some(objval) = objval {
+            self.accepted += 1;
+            self.last_objective = Some(objval);
+        }
+    }
+}
+
+impl<H: PrimalHeuristic> CallbackHandler for Heuristic<H> {
+    fn on_mip_node(&mut self, ctx: &MipNodeCtx) -> Result<()> {
+        if ctx.status() != Status::Optimal {
+            return Ok(());
+        }
+        if !self.next_node() {
+            return Ok(());
+        }
+
+        let vars = ctx.model().get_vars().cloned().collect_vec();
+        let relaxation = r#try!(SolutionView::node_relaxation(ctx, &vars));
+        let solution = match r#try!(self.heuristic.propose(ctx.model(), &relaxation)) {
+            Some(solution) => solution,
+            None => return Ok(()),
+        };
+
+        let objval = r#try!(ctx.set_solution(&solution.vars, &solution.values));
+        self.record(objval);
+        Ok(())
+    }
+}
+
+#[test]
+fn partial_solution_should_check_lengths() {
+    let x = Var::new(0);
+    let y = Var::new(1);
+    let sol = PartialSolution::new().set(&x, 1.0);
+    assert_eq!(sol.len(), 1);
+    let sol = sol.extend(&[y.clone()], &[0.0]).unwrap();
+    assert_eq!(sol.values, vec![1.0, 0.0]);
+    assert!(sol.vars[1] == y);
+    assert!(PartialSolution::new().extend(&[x, y], &[1.0]).is_err());
+    assert!(PartialSolution::new().is_empty());
+}
+
+#[test]
+fn heuristic_should_count_nodes_and_proposals() {
+    let propose = |_: ModelView, _: &SolutionView| -> Result<Option<PartialSolution>> { Ok(None) };
+    let mut heuristic = Heuristic::new(propose).frequency(0);
+    assert!(heuristic.next_node());
+    assert!(heuristic.next_node());
+
+    let mut heuristic = Heuristic::new(propose).frequency(3);
+    let called = (0..7).map(|_| heuristic.next_node()).collect_vec();
+    assert_eq!(called, vec![true, false, false, true, false, false, true]);
+
+    heuristic.record(None);
+    heuristic.record(Some(10.0));
+    heuristic.record(Some(8.0));
+    heuristic.record(None);
+    assert_eq!(heuristic.proposed(), 4);
+    assert_eq!(heuristic.accepted(), 2);
+    assert_eq!(heuristic.last_objective(), Some(8.0));
+}
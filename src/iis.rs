@@ -0,0 +1,437 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use crate::ffi;
+use itertools::{Itertools, Zip};
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use super::{Constr, ConstrSense, Model, QConstr, SOSType, Var, SOS};
+use crate::attr;
+use crate::error::Result;
+use crate::INFINITY;
+
+/// Forced membership of an element in the IIS computation
+///
+/// Use with the attributes `IISLBForce`, `IISUBForce`, `IISConstrForce`, `IISSOSForce` and
+/// `IISQConstrForce`, e.g. `c.set(&mut model, attr::IISConstrForce, IisForce::Include.into())`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum IisForce {
+    /// Let the IIS algorithm decide
+    Auto = -1,
+    /// Exclude the element from the IIS
+    Exclude = 0,
+    /// Always include the element in the IIS
+    Include = 1,
+}
+
+impl Into<i32> for IisForce {
+    fn into(self) -> i32 {
+        self as i32
+    }
+}
+
+/// Variable bounds which participate in an IIS
+#[derive(Debug, Clone)]
+pub struct IisBound {
+    /// Proxy object of the variable
+    pub var: Var,
+    /// Name of the variable
+    pub name: String,
+    /// The lower bound, if it participates in the IIS
+    pub lb: Option<f64>,
+    /// The upper bound, if it participates in the IIS
+    pub ub: Option<f64>,
+}
+
+/// A linear constraint which participates in an IIS
+#[derive(Debug, Clone)]
+pub struct IisConstr {
+    /// Proxy object of the constraint
+    pub constr: Constr,
+    /// Name of the constraint
+    pub name: String,
+    /// Sense of the constraint
+    pub sense: ConstrSense,
+    /// Right-hand side value
+    pub rhs: f64,
+    /// Left-hand side of the constraint as pairs of variable name and coefficient
+    pub row: Vec<(String, f64)>,
+}
+
+/// A quadratic constraint which participates in an IIS
+#[derive(Debug, Clone)]
+pub struct IisQConstr {
+    /// Proxy object of the constraint
+    pub qconstr: QConstr,
+    /// Name of the constraint
+    pub name: String,
+    /// Sense of the constraint
+    pub sense: ConstrSense,
+    /// Right-hand side value
+    pub rhs: f64,
+    /// Linear terms of the left-hand side as pairs of variable name and coefficient
+    pub row: Vec<(String, f64)>,
+    /// Quadratic terms of the left-hand side as triples of variable names and coefficient
+    pub qrow: Vec<(String, String, f64)>,
+}
+
+/// Irreducible Inconsistent Subsystem (IIS) of an infeasible model
+///
+/// Returned by [`Model::compute_iis()`](struct.Model.html#method.compute_iis).
+#[derive(Debug, Clone)]
+pub struct IisReport {
+    /// Whether the IIS is minimal (`IISMinimal`)
+    pub minimal: bool,
+    /// Variable bounds in the IIS
+    pub bounds: Vec<IisBound>,
+    /// Linear constraints in the IIS
+    pub constrs: Vec<IisConstr>,
+    /// Quadratic constraints in the IIS
+    pub qconstrs: Vec<IisQConstr>,
+    /// SOS constraints in the IIS, with their types and members (pairs of variable name and
+    /// weight)
+    pub sos: Vec<(SOS, SOSType, Vec<(String, f64)>)>,
+}
+
+impl IisReport {
+    /// Returns `true` if the IIS contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+            && self.constrs.is_empty()
+            && self.qconstrs.is_empty()
+            && self.sos.is_empty()
+    }
+
+    /// Write the IIS as a submodel to a file in the LP format (`.ilp`).
+    pub fn write(&self, filename: &str) -> Result<()> {
+        let file = r#try!(File::create(filename));
+        let mut writer = BufWriter::new(file);
+        r#try!(self.write_to(&mut writer));
+        r#try!(writer.flush());
+        Ok(())
+    }
+
+    /// Write the IIS as a submodel in the LP format.
+    ///
+    /// Variables whose bounds do not participate in the IIS are declared as free.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        r#try!(writeln!(writer, "\\ Irreducible Inconsistent Subsystem"));
+        r#try!(writeln!(writer, "Minimize"));
+        r#try!(writeln!(writer, "Subject To"));
+
+        let mut names = BTreeSet::new();
+        for c in self.constrs.iter() {
+            r#try!(write!(writer, " {}:", c.name));
+            for &(ref name, coeff) in c.row.iter() {
+                r#try!(write_term(writer, coeff, name));
+                names.insert(name.clone());
+            }
+            r#try!(writeln!(writer, " {} {}", lp_sense(c.sense), lp_num(c.rhs)));
+        }
+        for q in self.qconstrs.iter() {
+            r#try!(write!(writer, " {}:", q.name));
+            for &(ref name, coeff) in q.row.iter() {
+                r#try!(write_term(writer, coeff, name));
+                names.insert(name.clone());
+            }
+            if !q.qrow.is_empty() {
+                r#try!(write!(writer, " + ["));
+                for &(ref row, ref col, coeff) in q.qrow.iter() {
+                    if row == col {
+                        r#try!(write_term(writer, coeff, &format!("{} ^2", row)));
+                    } else {
+                        r#try!(write_term(writer, coeff, &format!("{} * {}", row, col)));
+                    }
+                    names.insert(row.clone());
+                    names.insert(col.clone());
+                }
+                r#try!(write!(writer, " ]"));
+            }
+            r#try!(writeln!(writer, " {} {}", lp_sense(q.sense), lp_num(q.rhs)));
+        }
+
+        r#try!(writeln!(writer, "Bounds"));
+        for b in self.bounds.iter() {
+            match (b.lb, b.ub) {
+                (Some(lb), Some(ub)) => r#try!(writeln!(
+                    writer,
+                    " {} <= {} <= {}",
+                    lp_num(lb),
+                    b.name,
+                    lp_num(ub)
+                )),
+                (Some(lb), None) => r#try!(writeln!(writer, " {} >= {}", b.name, lp_num(lb))),
+                (None, Some(ub)) => {
+                    r#try!(writeln!(writer, " -inf <= {} <= {}", b.name, lp_num(ub)))
+                }
+                (None, None) => r#try!(writeln!(writer, " {} free", b.name)),
+            }
+        }
+        for &(_, _, ref members) in self.sos.iter() {
+            for &(ref name, _) in members.iter() {
+                names.insert(name.clone());
+            }
+        }
+        for b in self.bounds.iter() {
+            names.remove(&b.name);
+        }
+        for name in names.iter() {
+            r#try!(writeln!(writer, " {} free", name));
+        }
+
+        if !self.sos.is_empty() {
+            r#try!(writeln!(writer, "SOS"));
+            for (i, &(_, sostype, ref members)) in self.sos.iter().enumerate() {
+                let sostype = match sostype {
+                    SOSType::SOSType1 => "S1",
+                    SOSType::SOSType2 => "S2",
+                };
+                r#try!(write!(writer, " sos{}: {} ::", i, sostype));
+                for &(ref name, weight) in members.iter() {
+                    r#try!(write!(writer, " {}:{}", name, weight));
+                }
+                r#try!(writeln!(writer));
+            }
+        }
+
+        r#try!(writeln!(writer, "End"));
+        Ok(())
+    }
+}
+
+fn lp_num(val: f64) -> String {
+    if val >= INFINITY {
+        "inf".to_owned()
+    } else if val <= -INFINITY {
+        "-inf".to_owned()
+    } else {
+        format!("{}", val)
+    }
+}
+
+fn lp_sense(sense: ConstrSense) -> &'static str {
+    match sense {
+        ConstrSense::Equal => "=",
+        ConstrSense::Less => "<=",
+        ConstrSense::Greater => ">=",
+    }
+}
+
+fn write_term<W: Write>(writer: &mut W, coeff: f64, name: &str) -> Result<()> {
+    if coeff < 0.0 {
+        r#try!(write!(writer, " - {} {}", -coeff, name));
+    } else {
+        r#try!(write!(writer, " + {} {}", coeff, name));
+    }
+    Ok(())
+}
+
+impl Model {
+    /// Compute an Irreducible Inconsistent Subsystem (IIS) of the model.
+    ///
+    /// Returns the bounds and constraints which form the IIS.
+    /// See also [`IisReport`](struct.IisReport.html).
+    pub fn compute_iis(&mut self) -> Result<IisReport> {
+        r#try!(self.update());
+        r#try!(self.check_apicall(unsafe { ffi::GRBcomputeIIS(self.model) }));
+        self.iis_report()
+    }
+
+    // collect the result of `GRBcomputeIIS()`.
+    fn iis_report(&self) -> Result<IisReport> {
+        let minimal = r#try!(self.get(attr::IISMinimal)) != 0;
+        let vnames = r#try!(self.get_values(attr::VarName, self.vars.as_slice()));
+
+        let mut bounds = Vec::new();
+        let iis_lb = r#try!(self.get_values(attr::IISLB, self.vars.as_slice()));
+        let iis_ub = r#try!(self.get_values(attr::IISUB, self.vars.as_slice()));
+        for (i, (v, lb, ub)) in Zip::new((self.vars.iter(), iis_lb, iis_ub)).enumerate() {
+            if lb == 0 && ub == 0 {
+                continue;
+            }
+            bounds.push(IisBound {
+                var: v.clone(),
+                name: vnames[i].clone(),
+                lb: if lb != 0 {
+                    Some(r#try!(v.get(self, attr::LB)))
+                } else {
+                    None
+                },
+                ub: if ub != 0 {
+                    Some(r#try!(v.get(self, attr::UB)))
+                } else {
+                    None
+                },
+            });
+        }
+
+        let mut constrs = Vec::new();
+        let iis_constr = r#try!(self.get_values(attr::IISConstr, self.constrs.as_slice()));
+        for (c, flag) in Zip::new((self.constrs.iter(), iis_constr)) {
+            if flag == 0 {
+                continue;
+            }
+            let row = r#try!(self.get_constr_terms(c))
+                .into_iter()
+                .map(|(j, coeff)| (vnames[j as usize].clone(), coeff))
+                .collect_vec();
+            constrs.push(IisConstr {
+                constr: c.clone(),
+                name: r#try!(c.get(self, attr::ConstrName)),
+                sense: r#try!(c.sense(self)),
+                rhs: r#try!(c.get(self, attr::RHS)),
+                row,
+            });
+        }
+
+        let mut qconstrs = Vec::new();
+        let iis_qconstr = r#try!(self.get_values(attr::IISQConstr, self.qconstrs.as_slice()));
+        for (q, flag) in Zip::new((self.qconstrs.iter(), iis_qconstr)) {
+            if flag == 0 {
+                continue;
+            }
+            let (lrow, qrow) = r#try!(self.get_qconstr_terms(q));
+            qconstrs.push(IisQConstr {
+                qconstr: q.clone(),
+                name: r#try!(q.get(self, attr::QCName)),
+                sense: r#try!(q.sense(self)),
+                rhs: r#try!(q.get(self, attr::QCRHS)),
+                row: lrow
+                    .into_iter()
+                    .map(|(j, coeff)| (vnames[j as usize].clone(), coeff))
+                    .collect(),
+                qrow: qrow
+                    .into_iter()
+                    .map(|(i, j, coeff)| {
+                        (
+                            vnames[i as usize].clone(),
+                            vnames[j as usize].clone(),
+                            coeff,
+                        )
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut sos = Vec::new();
+        let iis_sos = r#try!(self.get_values(attr::IISSOS, self.sos.as_slice()));
+        for (s, flag) in Zip::new((self.sos.iter(), iis_sos)) {
+            if flag == 0 {
+                continue;
+            }
+            let (sostype, members) = r#try!(self.get_sos_members(s));
+            let members = members
+                .into_iter()
+                .map(|(v, weight)| (vnames[v.index() as usize].clone(), weight))
+                .collect();
+            sos.push((s.clone(), sostype, members));
+        }
+
+        Ok(IisReport {
+            minimal,
+            bounds,
+            constrs,
+            qconstrs,
+            sos,
+        })
+    }
+}
+
+#[test]
+fn iis_report_should_be_written_in_lp_format() {
+    let report = IisReport {
+        minimal: true,
+        bounds: vec![],
+        constrs: vec![],
+        qconstrs: vec![],
+        sos: vec![],
+    };
+    let mut buf = Vec::new();
+    report.write_to(&mut buf).unwrap();
+    let text = String::from_utf8(buf).unwrap();
+    assert!(text.contains("Subject To"));
+    assert!(text.ends_with("End\n"));
+
+    let mut buf = Vec::new();
+    write_term(&mut buf, -2.5, "x").unwrap();
+    write_term(&mut buf, 1.0, "y").unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), " - 2.5 x + 1 y");
+    assert_eq!(lp_num(INFINITY), "inf");
+}
+
+#[test]
+fn iis_report_should_write_bounds_qconstrs_and_sos() {
+    let report = IisReport {
+        minimal: true,
+        bounds: vec![
+            IisBound {
+                var: Var::new(0),
+                name: "x".to_owned(),
+                lb: Some(0.0),
+                ub: Some(1.0),
+            },
+            IisBound {
+                var: Var::new(1),
+                name: "y".to_owned(),
+                lb: Some(2.0),
+                ub: None,
+            },
+            IisBound {
+                var: Var::new(2),
+                name: "z".to_owned(),
+                lb: None,
+                ub: Some(5.0),
+            },
+        ],
+        constrs: vec![IisConstr {
+            constr: Constr::new(0),
+            name: "c1".to_owned(),
+            sense: ConstrSense::Less,
+            rhs: 1.0,
+            row: vec![("x".to_owned(), 1.0), ("y".to_owned(), 1.0)],
+        }],
+        qconstrs: vec![IisQConstr {
+            qconstr: QConstr::new(0),
+            name: "q1".to_owned(),
+            sense: ConstrSense::Less,
+            rhs: 4.0,
+            row: vec![("w".to_owned(), -1.0)],
+            qrow: vec![
+                ("x".to_owned(), "x".to_owned(), 1.0),
+                ("x".to_owned(), "w".to_owned(), 2.0),
+            ],
+        }],
+        sos: vec![(
+            SOS::new(0),
+            SOSType::SOSType1,
+            vec![("u".to_owned(), 1.0), ("v".to_owned(), 2.0)],
+        )],
+    };
+    assert!(!report.is_empty());
+
+    let mut buf = Vec::new();
+    report.write_to(&mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        "\\ Irreducible Inconsistent Subsystem\n\
+         Minimize\n\
+         Subject To\n \
+         c1: + 1 x + 1 y <= 1\n \
+         q1: - 1 w + [ + 1 x ^2 + 2 x * w ] <= 4\n\
+         Bounds\n \
+         0 <= x <= 1\n \
+         y >= 2\n \
+         -inf <= z <= 5\n \
+         u free\n \
+         v free\n \
+         w free\n\
+         SOS\n \
+         sos0: S1 :: u:1 v:2\n\
+         End\n"
+    );
+}
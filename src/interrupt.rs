@@ -0,0 +1,174 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use crate::ffi;
+
+use std::ptr::null_mut;
+#[cfg(all(unix, feature = "signal-hook"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::Model;
+#[cfg(all(unix, feature = "signal-hook"))]
+use crate::error::Result;
+
+struct RawModel(*mut ffi::GRBmodel);
+
+// the pointer is only used to call `GRBterminate()`, which may be called from any thread.
+unsafe impl Send for RawModel {}
+
+/// Handle to terminate the optimization of a model from another thread
+///
+/// Created by [`Model::interrupt_handle()`](struct.Model.html#method.interrupt_handle). The
+/// handle is invalidated when the model is dropped, so it is safe to keep it after that.
+///
+/// # Example
+/// ```ignore
+/// let handle = model.interrupt_handle();
+/// thread::spawn(move || {
+///     thread::sleep(Duration::from_secs(60));
+///     handle.interrupt();
+/// });
+/// model.optimize()?;
+/// assert_eq!(model.status()?, Status::Interrupted);
+/// ```
+#[derive(Clone)]
+pub struct InterruptHandle {
+    model: Arc<Mutex<RawModel>>,
+}
+
+impl InterruptHandle {
+    pub(crate) fn new(model: *mut ffi::GRBmodel) -> InterruptHandle {
+        InterruptHandle {
+            model: Arc::new(Mutex::new(RawModel(model))),
+        }
+    }
+
+    /// Send a request to terminate the current optimization of the model.
+    ///
+    /// Returns `false` if the model has already been dropped.
+    pub fn interrupt(&self) -> bool {
+        let model = self.lock();
+        if model.0.is_null() {
+            return false;
+        }
+        unsafe { ffi::GRBterminate(model.0) };
+        true
+    }
+
+    /// Returns `true` if the model is still alive.
+    pub fn is_alive(&self) -> bool {
+        !self.lock().0.is_null()
+    }
+
+    // called before the model is freed.
+    pub(crate) fn invalidate(&self) {
+        self.lock().0 = null_mut();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RawModel> {
+        // the lock is never held while panicking, but recover the pointer anyway.
+        self.model.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Interrupt the optimization on SIGINT (Ctrl-C) until the returned guard is dropped.
+    ///
+    /// The signal is handled by a background thread. Once all the guards are dropped, SIGINT
+    /// terminates the process as by default, even if another handler had been installed before
+    /// without signal-hook. Available with the `signal-hook` feature on Unix.
+    #[cfg(all(unix, feature = "signal-hook"))]
+    pub fn on_sigint(&self) -> Result<SigintGuard> {
+        use signal_hook::consts::SIGINT;
+        use signal_hook::iterator::Signals;
+
+        r#try!(register_sigint_default());
+        let mut signals = r#try!(Signals::new([SIGINT]));
+        let close = signals.handle();
+        let handle = self.clone();
+        let thread = std::thread::spawn(move || {
+            for _ in signals.forever() {
+                handle.interrupt();
+            }
+        });
+        SIGINT_GUARDS.fetch_add(1, Ordering::SeqCst);
+        Ok(SigintGuard {
+            close,
+            thread: Some(thread),
+        })
+    }
+}
+
+// number of the living `SigintGuard`s.
+#[cfg(all(unix, feature = "signal-hook"))]
+static SIGINT_GUARDS: AtomicUsize = AtomicUsize::new(0);
+
+// signal-hook never restores the previous handler when a registration is removed, so a handler
+// which performs the default action while no guard lives is registered once per process.
+#[cfg(all(unix, feature = "signal-hook"))]
+fn register_sigint_default() -> Result<()> {
+    use signal_hook::consts::SIGINT;
+    use signal_hook::low_level;
+
+    static REGISTERED: Mutex<bool> = Mutex::new(false);
+    let mut registered = REGISTERED.lock().unwrap_or_else(|e| e.into_inner());
+    if !*registered {
+        // the action only uses async-signal-safe operations.
+        r#try!(unsafe {
+            low_level::register(SIGINT, || {
+                if SIGINT_GUARDS.load(Ordering::SeqCst) == 0 {
+                    let _ = low_level::emulate_default_handler(SIGINT);
+                }
+            })
+        });
+        *registered = true;
+    }
+    Ok(())
+}
+
+/// Guard which interrupts the optimization on SIGINT while it lives
+///
+/// Created by [`InterruptHandle::on_sigint()`](struct.InterruptHandle.html#method.on_sigint).
+#[cfg(all(unix, feature = "signal-hook"))]
+pub struct SigintGuard {
+    close: signal_hook::iterator::Handle,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(all(unix, feature = "signal-hook"))]
+impl Drop for SigintGuard {
+    fn drop(&mut self) {
+        SIGINT_GUARDS.fetch_sub(1, Ordering::SeqCst);
+        self.close.close();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Model {
+    /// Retrieve a handle to terminate the optimization from another thread.
+    pub fn interrupt_handle(&self) -> InterruptHandle {
+        self.interrupt.clone()
+    }
+
+    /// Optimize the model, interrupting it on SIGINT (Ctrl-C).
+    ///
+    /// After an interruption the status is `Interrupted` and the incumbent solution is
+    /// available. Available with the `signal-hook` feature on Unix.
+    #[cfg(all(unix, feature = "signal-hook"))]
+    pub fn optimize_interruptible(&mut self) -> Result<()> {
+        let _guard = r#try!(self.interrupt_handle().on_sigint());
+        self.optimize()
+    }
+}
+
+#[test]
+fn interrupt_handle_should_be_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync + Clone>(_: &T) {}
+    let handle = InterruptHandle::new(null_mut());
+    assert_send_sync(&handle);
+    assert!(!handle.is_alive());
+    assert!(!handle.interrupt());
+}
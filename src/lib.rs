@@ -82,6 +82,8 @@
 
 extern crate gurobi_sys as ffi;
 extern crate itertools;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 mod env;
 mod error;
@@ -97,10 +99,31 @@ mod attribute;
 // re-exports
 pub use crate::error::{Error, Result};
 
-pub use crate::env::Env;
+pub use crate::env::{Env, ParamGuard};
 
-pub use crate::model::callback::{Callback, Where};
+pub use crate::model::basis::{Basis, BasisStatus, WarmStart};
+pub use crate::model::callback::{Callback, Context, ModelView, Where};
+pub use crate::model::callback::{MessageCtx, MipNodeCtx, MipSolCtx, MultiObjCtx, ProgressCtx};
+pub use crate::model::conflict::{Conflict, ConflictFinder, ConflictGroup, FilterMethod};
 pub use crate::model::expr::{LinExpr, QuadExpr};
+pub use crate::model::feasrelax::{
+    FeasRelax, Relaxation, RelaxationReport, RelaxedElement, Violation,
+};
+pub use crate::model::handler::{CallbackChain, CallbackHandler};
+pub use crate::model::heuristic::{Heuristic, PartialSolution, PrimalHeuristic};
+pub use crate::model::iis::{IisBound, IisConstr, IisForce, IisQConstr, IisReport};
+pub use crate::model::interrupt::InterruptHandle;
+#[cfg(all(unix, feature = "signal-hook"))]
+pub use crate::model::interrupt::SigintGuard;
+pub use crate::model::outcome::SolveOutcome;
+pub use crate::model::sensitivity::{ConstrSensitivity, SensitivityReport, VarSensitivity};
+pub use crate::model::separation::{Cut, Separation, SeparationMode, SeparationRound};
+pub use crate::model::separation::{Separator, SolutionKind, SolutionView};
+pub use crate::model::simplex::SparseVec;
+pub use crate::model::termination::{And, Or, Progress, Termination, TerminationPolicy};
+pub use crate::model::termination::{Cancelled, GapBelow, NoImprovement, ObjectiveTarget, TimeElapsed};
+pub use crate::model::trace::{SolveTrace, TraceEvent, TraceIncumbent, TracePoint, TraceRecorder};
+pub use crate::model::tune::{TuneOptions, TuneResults};
 pub use crate::model::ConstrSense::*;
 pub use crate::model::ModelSense::*;
 pub use crate::model::RelaxType::*;
@@ -111,10 +134,14 @@ pub use crate::model::{ConstrSense, ModelSense, RelaxType, SOSType, Status, VarT
 
 pub use crate::attribute::exports as attr;
 pub use crate::parameter::exports as param;
+pub use crate::parameter::ParamSet;
 
 /// Large number used in C API
 pub const INFINITY: f64 = 1e100;
 
+/// Marker of an undefined value, e.g. a variable left free in a partial solution
+pub const UNDEFINED: f64 = 1e101;
+
 /// Returns the version number of Gurobi
 pub fn version() -> (i32, i32, i32) {
     let (mut major, mut minor, mut technical) = (0, 0, 0);
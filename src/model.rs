@@ -3,61 +3,130 @@
 // This software is released under the MIT License.
 // See http://opensource.org/licenses/mit-license.php or <LICENSE>.
 
+#[path = "basis.rs"]
+pub mod basis;
 #[path = "callback.rs"]
 pub mod callback;
+#[path = "conflict.rs"]
+pub mod conflict;
 #[path = "expr.rs"]
 pub mod expr;
+#[path = "feasrelax.rs"]
+pub mod feasrelax;
+#[path = "handler.rs"]
+pub mod handler;
+#[path = "heuristic.rs"]
+pub mod heuristic;
+#[path = "iis.rs"]
+pub mod iis;
+#[path = "interrupt.rs"]
+pub mod interrupt;
+#[path = "outcome.rs"]
+pub mod outcome;
+#[path = "sensitivity.rs"]
+pub mod sensitivity;
+#[path = "separation.rs"]
+pub mod separation;
+#[path = "simplex.rs"]
+pub mod simplex;
+#[path = "termination.rs"]
+pub mod termination;
+#[path = "trace.rs"]
+pub mod trace;
+#[path = "tune.rs"]
+pub mod tune;
 
 use crate::ffi;
 use itertools::{Itertools, Zip};
 
+use std::any::Any;
 use std::cell::Cell;
+use std::convert::TryFrom;
 use std::ffi::CString;
 use std::iter;
 use std::mem::transmute;
 use std::ops::{Deref, DerefMut};
-use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::ptr::{null, null_mut};
 use std::rc::Rc;
 use std::slice::Iter;
 
 use self::callback::{Callback, New};
 use self::expr::{LinExpr, QuadExpr};
+use self::interrupt::InterruptHandle;
 use crate::attr;
 use crate::attribute::{Attr, AttrArray};
-use crate::env::{Env, EnvAPI};
+use crate::env::{Env, EnvAPI, EnvHolder, ParamGuard};
 use crate::error::{Error, Result};
+use crate::parameter::ParamSet;
 use crate::util;
 
 /// Type for new variable
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum VarType {
     Binary,
     Continuous,
     Integer,
+    /// Takes the value zero or a continuous value between its bounds
+    SemiContinuous,
+    /// Takes the value zero or an integral value between its bounds
+    SemiInteger,
 }
 
-impl Into<ffi::c_char> for VarType {
-    fn into(self) -> ffi::c_char {
+impl VarType {
+    // the range of semi-continuous/semi-integer variables must not be empty. an infinite upper
+    // bound is accepted by Gurobi, although a finite one gives a tighter formulation.
+    fn check_bounds(self, lb: f64, ub: f64) -> Result<()> {
         match self {
+            VarType::SemiContinuous | VarType::SemiInteger if lb > ub => {
+                Err(Error::InvalidValue(format!(
+                    "bounds of {:?} variable: lb = {} is greater than ub = {}",
+                    self, lb, ub
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<VarType> for ffi::c_char {
+    fn from(vtype: VarType) -> ffi::c_char {
+        match vtype {
             VarType::Binary => 'B' as ffi::c_char,
             VarType::Continuous => 'C' as ffi::c_char,
             VarType::Integer => 'I' as ffi::c_char,
+            VarType::SemiContinuous => 'S' as ffi::c_char,
+            VarType::SemiInteger => 'N' as ffi::c_char,
+        }
+    }
+}
+
+impl TryFrom<ffi::c_char> for VarType {
+    type Error = Error;
+    fn try_from(val: ffi::c_char) -> Result<VarType> {
+        match val as u8 as char {
+            'B' => Ok(VarType::Binary),
+            'C' => Ok(VarType::Continuous),
+            'I' => Ok(VarType::Integer),
+            'S' => Ok(VarType::SemiContinuous),
+            'N' => Ok(VarType::SemiInteger),
+            c => Err(Error::InvalidValue(format!("variable type `{}`", c))),
         }
     }
 }
 
 /// Sense for new linear/quadratic constraint
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConstrSense {
     Equal,
     Greater,
     Less,
 }
 
-impl Into<ffi::c_char> for ConstrSense {
-    fn into(self) -> ffi::c_char {
-        match self {
+impl From<ConstrSense> for ffi::c_char {
+    fn from(sense: ConstrSense) -> ffi::c_char {
+        match sense {
             ConstrSense::Equal => '=' as ffi::c_char,
             ConstrSense::Less => '<' as ffi::c_char,
             ConstrSense::Greater => '>' as ffi::c_char,
@@ -65,6 +134,50 @@ impl Into<ffi::c_char> for ConstrSense {
     }
 }
 
+impl TryFrom<ffi::c_char> for ConstrSense {
+    type Error = Error;
+    fn try_from(val: ffi::c_char) -> Result<ConstrSense> {
+        match val as u8 as char {
+            '=' => Ok(ConstrSense::Equal),
+            '<' => Ok(ConstrSense::Less),
+            '>' => Ok(ConstrSense::Greater),
+            c => Err(Error::InvalidValue(format!("constraint sense `{}`", c))),
+        }
+    }
+}
+
+#[test]
+fn vartype_and_sense_conversion_roundtrip() {
+    for &vtype in [
+        VarType::Binary,
+        VarType::Continuous,
+        VarType::Integer,
+        VarType::SemiContinuous,
+        VarType::SemiInteger,
+    ]
+    .iter()
+    {
+        let raw: ffi::c_char = vtype.into();
+        assert_eq!(VarType::try_from(raw).unwrap(), vtype);
+    }
+    for &sense in [ConstrSense::Equal, ConstrSense::Greater, ConstrSense::Less].iter() {
+        let raw: ffi::c_char = sense.into();
+        assert_eq!(ConstrSense::try_from(raw).unwrap(), sense);
+    }
+    assert!(VarType::try_from('X' as ffi::c_char).is_err());
+    assert!(ConstrSense::try_from('!' as ffi::c_char).is_err());
+}
+
+#[test]
+fn semi_variable_bounds_should_be_checked() {
+    assert!(VarType::SemiContinuous.check_bounds(2.0, 10.0).is_ok());
+    assert!(VarType::SemiInteger
+        .check_bounds(1.0, super::INFINITY)
+        .is_ok());
+    assert!(VarType::SemiContinuous.check_bounds(5.0, 1.0).is_err());
+    assert!(VarType::Continuous.check_bounds(5.0, 1.0).is_ok());
+}
+
 /// Sense of new objective function
 #[derive(Debug, Copy, Clone)]
 pub enum ModelSense {
@@ -86,7 +199,7 @@ fn modelsense_conversion_success() {
 }
 
 /// Type of new SOS constraint
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum SOSType {
     SOSType1 = 1,
     SOSType2 = 2,
@@ -98,34 +211,162 @@ impl Into<i32> for SOSType {
     }
 }
 
+impl SOSType {
+    fn from_raw(val: i32) -> Result<SOSType> {
+        match val {
+            1 => Ok(SOSType::SOSType1),
+            2 => Ok(SOSType::SOSType2),
+            _ => Err(Error::InvalidValue(format!("SOS type {}", val))),
+        }
+    }
+}
+
 /// Status of a model
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// Status codes which are unknown to this crate (e.g. introduced by a newer version of Gurobi)
+/// are reported as `Unknown`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
-    Loaded = 1,
+    /// Model is loaded, but no solution information is available
+    Loaded,
+    /// Model was solved to optimality
     Optimal,
+    /// Model was proven to be infeasible
     Infeasible,
+    /// Model was proven to be either infeasible or unbounded
     InfOrUnbd,
+    /// Model was proven to be unbounded
     Unbounded,
+    /// Optimal objective was proven to be worse than the `Cutoff` parameter
     CutOff,
+    /// `IterationLimit` or `BarIterLimit` was exceeded
     IterationLimit,
+    /// `NodeLimit` was exceeded
     NodeLimit,
+    /// `TimeLimit` was exceeded
     TimeLimit,
+    /// `SolutionLimit` was reached
     SolutionLimit,
+    /// Optimization was terminated by the user
     Interrupted,
+    /// Optimization was terminated due to numerical difficulties
     Numeric,
+    /// Unable to satisfy optimality tolerances, but a sub-optimal solution is available
     SubOptimal,
+    /// An asynchronous optimization call was made, but it has not completed yet
     InProgress,
+    /// The user-specified objective limit (`BestObjStop` / `BestBdStop`) was reached
+    UserObjLimit,
+    /// `WorkLimit` was exceeded
+    WorkLimit,
+    /// `MemLimit` or `SoftMemLimit` was exceeded
+    MemLimit,
+    /// A status code which is not known to this crate
+    Unknown(i32),
 }
 
 impl From<i32> for Status {
     fn from(val: i32) -> Status {
         match val {
-            1..=14 => unsafe { transmute(val as i8) },
-            _ => panic!("cannot convert to Status: {}", val),
+            1 => Status::Loaded,
+            2 => Status::Optimal,
+            3 => Status::Infeasible,
+            4 => Status::InfOrUnbd,
+            5 => Status::Unbounded,
+            6 => Status::CutOff,
+            7 => Status::IterationLimit,
+            8 => Status::NodeLimit,
+            9 => Status::TimeLimit,
+            10 => Status::SolutionLimit,
+            11 => Status::Interrupted,
+            12 => Status::Numeric,
+            13 => Status::SubOptimal,
+            14 => Status::InProgress,
+            15 => Status::UserObjLimit,
+            16 => Status::WorkLimit,
+            17 => Status::MemLimit,
+            val => Status::Unknown(val),
         }
     }
 }
 
+impl From<Status> for i32 {
+    fn from(status: Status) -> i32 {
+        match status {
+            Status::Loaded => 1,
+            Status::Optimal => 2,
+            Status::Infeasible => 3,
+            Status::InfOrUnbd => 4,
+            Status::Unbounded => 5,
+            Status::CutOff => 6,
+            Status::IterationLimit => 7,
+            Status::NodeLimit => 8,
+            Status::TimeLimit => 9,
+            Status::SolutionLimit => 10,
+            Status::Interrupted => 11,
+            Status::Numeric => 12,
+            Status::SubOptimal => 13,
+            Status::InProgress => 14,
+            Status::UserObjLimit => 15,
+            Status::WorkLimit => 16,
+            Status::MemLimit => 17,
+            Status::Unknown(val) => val,
+        }
+    }
+}
+
+impl Status {
+    /// Returns `true` if the status guarantees that a feasible solution is available.
+    ///
+    /// A solution may also be available when a limit was reached, including `UserObjLimit`
+    /// (which is also reached by `BestBdStop` without a solution); query `SolCount` for those.
+    pub fn has_solution(self) -> bool {
+        matches!(
+            self,
+            Status::Optimal | Status::SubOptimal | Status::SolutionLimit
+        )
+    }
+
+    /// Returns `true` if the optimization stopped because of a user-specified limit.
+    pub fn is_limit(self) -> bool {
+        matches!(
+            self,
+            Status::IterationLimit
+                | Status::NodeLimit
+                | Status::TimeLimit
+                | Status::SolutionLimit
+                | Status::UserObjLimit
+                | Status::WorkLimit
+                | Status::MemLimit
+        )
+    }
+
+    /// Returns `true` if the model was proven to be infeasible and/or unbounded.
+    pub fn is_infeasible_or_unbounded(self) -> bool {
+        matches!(
+            self,
+            Status::Infeasible | Status::InfOrUnbd | Status::Unbounded
+        )
+    }
+}
+
+#[test]
+fn status_conversion_should_not_panic() {
+    use std::convert::TryFrom;
+    for val in 1..18 {
+        let status = Status::try_from(val).unwrap();
+        assert!(status != Status::Unknown(val));
+        assert_eq!(i32::from(status), val);
+    }
+    assert_eq!(Status::from(18), Status::Unknown(18));
+    assert_eq!(Status::from(0), Status::Unknown(0));
+    assert!(Status::from(15).is_limit());
+    assert!(Status::from(13).has_solution());
+    assert!(!Status::UserObjLimit.has_solution());
+    assert!(Status::InfOrUnbd.is_infeasible_or_unbounded());
+}
+
 /// Type of cost function at feasibility relaxation
 #[derive(Debug, Copy, Clone)]
 pub enum RelaxType {
@@ -230,25 +471,81 @@ impl Var {
         let vtype = vtype as u8 as char;
         Ok((vtype, lb, ub))
     }
+
+    /// Query the type of the variable.
+    pub fn vtype(&self, model: &Model) -> Result<VarType> {
+        VarType::try_from(r#try!(self.get(model, attr::VType)))
+    }
+
+    /// Change the type of the variable.
+    ///
+    /// For semi-continuous and semi-integer types, the current bounds of the variable are
+    /// validated.
+    pub fn set_vtype(&self, model: &mut Model, vtype: VarType) -> Result<()> {
+        if let VarType::SemiContinuous | VarType::SemiInteger = vtype {
+            let lb = r#try!(self.get(model, attr::LB));
+            let ub = r#try!(self.get(model, attr::UB));
+            r#try!(vtype.check_bounds(lb, ub));
+        }
+        self.set(model, attr::VType, vtype.into())
+    }
 }
 
 /// Proxy object of a linear constraint
 #[derive(Clone, Debug)]
 pub struct Constr(Proxy);
 
+impl Constr {
+    /// Query the sense of the constraint.
+    pub fn sense(&self, model: &Model) -> Result<ConstrSense> {
+        ConstrSense::try_from(r#try!(self.get(model, attr::Sense)))
+    }
+
+    /// Change the sense of the constraint.
+    pub fn set_sense(&self, model: &mut Model, sense: ConstrSense) -> Result<()> {
+        self.set(model, attr::Sense, sense.into())
+    }
+}
+
 /// Proxy object of a quadratic constraint
 #[derive(Clone, Debug)]
 pub struct QConstr(Proxy);
 
+impl QConstr {
+    /// Query the sense of the quadratic constraint.
+    pub fn sense(&self, model: &Model) -> Result<ConstrSense> {
+        ConstrSense::try_from(r#try!(self.get(model, attr::QCSense)))
+    }
+
+    /// Change the sense of the quadratic constraint.
+    pub fn set_sense(&self, model: &mut Model, sense: ConstrSense) -> Result<()> {
+        self.set(model, attr::QCSense, sense.into())
+    }
+}
+
 /// Proxy object of a Special Order Set (SOS) constraint
 #[derive(Clone, Debug)]
 pub struct SOS(Proxy);
 
+impl SOS {
+    /// Returns the type of the SOS constraint and its members with their weights.
+    pub fn members(&self, model: &Model) -> Result<(SOSType, Vec<(Var, f64)>)> {
+        model.get_sos_members(self)
+    }
+}
+
 impl_traits_for_proxy! { Var Constr QConstr SOS }
 
+// the first failure in a callback, which is reported after the optimization.
+enum CallbackFailure {
+    Error(Error),
+    Panic(Box<dyn Any + Send>),
+}
+
 struct CallbackData<'a> {
     model: &'a Model,
     callback: &'a mut dyn FnMut(Callback) -> Result<()>,
+    failure: Option<CallbackFailure>,
 }
 
 #[allow(unused_variables)]
@@ -260,26 +557,23 @@ extern "C" fn callback_wrapper(
     usrdata: *mut ffi::c_void,
 ) -> ffi::c_int {
     let usrdata = unsafe { transmute::<_, &mut CallbackData>(usrdata) };
+    if usrdata.failure.is_some() {
+        // the optimization is being terminated.
+        return 0;
+    }
     let (callback, model) = (&mut usrdata.callback, &usrdata.model);
 
-    match Callback::new(cbdata, loc.into(), model) {
-        Err(err) => {
-            println!("failed to create context: {:?}", err);
-            -3
-        }
-        Ok(context) => {
-            match catch_unwind(AssertUnwindSafe(|| {
-                if callback(context).is_ok() {
-                    0
-                } else {
-                    -1
-                }
-            })) {
-                Ok(ret) => ret,
-                Err(e) => -3000,
-            }
-        }
-    }
+    let failure = match Callback::new(cbdata, loc, model) {
+        Err(err) => CallbackFailure::Error(err),
+        Ok(context) => match catch_unwind(AssertUnwindSafe(|| callback(context))) {
+            Ok(Ok(())) => return 0,
+            Ok(Err(err)) => CallbackFailure::Error(err),
+            Err(payload) => CallbackFailure::Panic(payload),
+        },
+    };
+    usrdata.failure = Some(failure);
+    usrdata.model.terminate();
+    0
 }
 
 #[allow(unused_variables)]
@@ -292,6 +586,32 @@ extern "C" fn null_callback_wrapper(
     0
 }
 
+// clears the callback from the model when the optimization finishes, even if it failed or
+// unwound, so that the model never keeps a pointer to the callback on the caller's stack.
+struct CallbackGuard<'a> {
+    model: &'a Model,
+    active: bool,
+}
+
+impl<'a> CallbackGuard<'a> {
+    fn clear(mut self) -> Result<()> {
+        self.active = false;
+        // Notice: Rust does not have approproate mechanism which treats "null" C-style function
+        // pointer.
+        self.model.check_apicall(unsafe {
+            ffi::GRBsetcallbackfunc(self.model.model, null_callback_wrapper, null_mut())
+        })
+    }
+}
+
+impl<'a> Drop for CallbackGuard<'a> {
+    fn drop(&mut self) {
+        if self.active {
+            unsafe { ffi::GRBsetcallbackfunc(self.model.model, null_callback_wrapper, null_mut()) };
+        }
+    }
+}
+
 /// Gurobi model object associated with certain environment.
 pub struct Model {
     model: *mut ffi::GRBmodel,
@@ -301,6 +621,7 @@ pub struct Model {
     constrs: Vec<Constr>,
     qconstrs: Vec<QConstr>,
     sos: Vec<SOS>,
+    interrupt: InterruptHandle,
 }
 
 pub trait FromRaw {
@@ -329,6 +650,7 @@ impl FromRaw for Model {
             constrs: Vec::new(),
             qconstrs: Vec::new(),
             sos: Vec::new(),
+            interrupt: InterruptHandle::new(model),
         };
         r#try!(model.populate());
         Ok(model)
@@ -461,6 +783,22 @@ impl Model {
         &mut self.env
     }
 
+    /// Override parameters of the model's environment until the returned guard is dropped.
+    ///
+    /// See also [`Env::with_params()`](struct.Env.html#method.with_params).
+    ///
+    /// # Example
+    /// ```ignore
+    /// {
+    ///     let mut model = model.with_params(&[(param::MIPGap, 0.05)][..])?;
+    ///     model.optimize()?;
+    /// }
+    /// // the previous MIP gap is restored here.
+    /// ```
+    pub fn with_params<S: Into<ParamSet>>(&mut self, params: S) -> Result<ParamGuard<Model>> {
+        ParamGuard::new(self, params.into())
+    }
+
     fn remove_items<P: DerefMut<Target = Proxy> + Clone>(vec: &[P]) -> (Vec<P>, Vec<i32>) {
         let (added, removed): (Vec<_>, _) = vec.iter().cloned().partition(|v| v.index() >= -1);
 
@@ -553,28 +891,54 @@ impl Model {
     }
 
     /// Optimize the model with a callback function
+    ///
+    /// The callback is only called during this optimization, so it may borrow data from the
+    /// caller's stack.
+    ///
+    /// If the callback returns an error, the optimization is terminated and the error is returned
+    /// as it is. Errors of other types can be returned from the callback by wrapping them into
+    /// `Error::Callback`. If the callback panics, the optimization is terminated and the panic is
+    /// resumed after the callback is cleared from the model.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let vars: Vec<Var> = model.get_vars().cloned().collect();
+    /// let mut incumbents = Vec::new();
+    /// model.optimize_with_callback(|cb| {
+    ///     if let Context::MIPSol(ctx) = cb.context() {
+    ///         incumbents.push(ctx.solution(&vars)?);
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// println!("{} incumbents found", incumbents.len());
+    /// ```
     #[allow(useless_transmute)] // Clippy gives a false positive here.
     pub fn optimize_with_callback<F>(&mut self, mut callback: F) -> Result<()>
     where
-        F: FnMut(Callback) -> Result<()> + 'static,
+        F: FnMut(Callback) -> Result<()>,
     {
         r#try!(self.update());
-        let usrdata = CallbackData {
+        let mut usrdata = CallbackData {
             model: self,
             callback: &mut callback,
+            failure: None,
         };
         r#try!(self.check_apicall(unsafe {
-            ffi::GRBsetcallbackfunc(self.model, callback_wrapper, transmute(&usrdata))
+            ffi::GRBsetcallbackfunc(self.model, callback_wrapper, transmute(&mut usrdata))
         }));
+        let guard = CallbackGuard {
+            model: self,
+            active: true,
+        };
 
-        r#try!(self.check_apicall(unsafe { ffi::GRBoptimize(self.model) }));
+        let error = unsafe { ffi::GRBoptimize(self.model) };
+        r#try!(guard.clear());
 
-        // clear callback from the model.
-        // Notice: Rust does not have approproate mechanism which treats "null" C-style function
-        // pointer.
-        self.check_apicall(unsafe {
-            ffi::GRBsetcallbackfunc(self.model, null_callback_wrapper, null_mut())
-        })
+        match usrdata.failure {
+            Some(CallbackFailure::Error(err)) => Err(err),
+            Some(CallbackFailure::Panic(payload)) => resume_unwind(payload),
+            None => self.check_apicall(error),
+        }
     }
 
     /// Wait for a optimization called asynchronously.
@@ -582,11 +946,6 @@ impl Model {
         self.check_apicall(unsafe { ffi::GRBsync(self.model) })
     }
 
-    /// Compute an Irreducible Inconsistent Subsystem (IIS) of the model.
-    pub fn compute_iis(&mut self) -> Result<()> {
-        self.check_apicall(unsafe { ffi::GRBcomputeIIS(self.model) })
-    }
-
     /// Send a request to the model to terminate the current optimization process.
     pub fn terminate(&self) {
         unsafe { ffi::GRBterminate(self.model) }
@@ -607,6 +966,10 @@ impl Model {
     }
 
     /// Prepare to retrieve the results of `tune()`.
+    ///
+    /// The `n`-th parameter set is loaded into the environment of the model.
+    /// See also [`tune_with()`](#method.tune_with) which retrieves the results as
+    /// [`ParamSet`](struct.ParamSet.html)s.
     /// See also references [on official
     /// manual](https://www.gurobi.com/documentation/6.5/refman/parameter_tuning_tool.html#sec:Tuning).
     pub fn get_tune_result(&self, n: i32) -> Result<()> {
@@ -677,6 +1040,7 @@ impl Model {
         if colconstrs.len() != colvals.len() {
             return Err(Error::InconsitentDims);
         }
+        r#try!(vtype.check_bounds(lb, ub));
 
         let colconstrs = {
             let mut buf = Vec::with_capacity(colconstrs.len());
@@ -735,6 +1099,9 @@ impl Model {
         {
             return Err(Error::InconsitentDims);
         }
+        for (&vtype, &lb, &ub) in Zip::new((vtypes, lbs, ubs)) {
+            r#try!(vtype.check_bounds(lb, ub));
+        }
 
         let names = {
             let mut buf = Vec::with_capacity(names.len());
@@ -1274,6 +1641,9 @@ impl Model {
     /// ## Returns
     /// * The objective value for the relaxation performed (if `minrelax` is `true`).
     /// * Slack variables for relaxation and linear/quadratic constraints related to theirs.
+    ///
+    /// See also [`FeasRelax`](struct.FeasRelax.html), which allows to set penalties per group
+    /// of constraints and reports the violations.
     pub fn feas_relax(
         &mut self,
         relaxtype: RelaxType,
@@ -1391,6 +1761,43 @@ impl Model {
         self.update()
     }
 
+    /// Retrieve the piecewise-linear objective function of the variable.
+    ///
+    /// Returns the points `(x, y)` set by [`set_pwl_obj()`](#method.set_pwl_obj), or `None` if the
+    /// variable has no piecewise-linear objective.
+    pub fn get_pwl_obj(&self, var: &Var) -> Result<Option<(Vec<f64>, Vec<f64>)>> {
+        if var.index() < 0 {
+            return Err(Error::InconsitentDims);
+        }
+
+        let mut npoints = 0;
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetpwlobj(
+                self.model,
+                var.index(),
+                &mut npoints,
+                null_mut(),
+                null_mut(),
+            )
+        }));
+        if npoints == 0 {
+            return Ok(None);
+        }
+
+        let mut x = vec![0.0; npoints as usize];
+        let mut y = vec![0.0; npoints as usize];
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetpwlobj(
+                self.model,
+                var.index(),
+                &mut npoints,
+                x.as_mut_ptr(),
+                y.as_mut_ptr(),
+            )
+        }));
+        Ok(Some((x, y)))
+    }
+
     /// Retrieve the status of the model.
     pub fn status(&self) -> Result<Status> {
         self.get(attr::Status).map(|val| val.into())
@@ -1421,6 +1828,169 @@ impl Model {
         item.remove()
     }
 
+    fn get_sos_members(&self, sos: &SOS) -> Result<(SOSType, Vec<(Var, f64)>)> {
+        if sos.index() < 0 {
+            return Err(Error::InconsitentDims);
+        }
+
+        let mut nummembers = 0;
+        let mut sostype = 0;
+        let mut beg = 0;
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetsos(
+                self.model,
+                &mut nummembers,
+                &mut sostype,
+                &mut beg,
+                null_mut(),
+                null_mut(),
+                sos.index(),
+                1,
+            )
+        }));
+
+        let mut ind = vec![0; nummembers as usize];
+        let mut weight = vec![0.0; nummembers as usize];
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetsos(
+                self.model,
+                &mut nummembers,
+                &mut sostype,
+                &mut beg,
+                ind.as_mut_ptr(),
+                weight.as_mut_ptr(),
+                sos.index(),
+                1,
+            )
+        }));
+
+        let sostype = r#try!(SOSType::from_raw(sostype));
+        let members = Zip::new((ind, weight))
+            .map(|(i, w)| (self.vars[i as usize].clone(), w))
+            .collect_vec();
+        Ok((sostype, members))
+    }
+
+    // retrieve the nonzero coefficients of a linear constraint as pairs of column index and value.
+    fn get_constr_terms(&self, constr: &Constr) -> Result<Vec<(i32, f64)>> {
+        if constr.index() < 0 {
+            return Err(Error::InconsitentDims);
+        }
+
+        let mut numnz = 0;
+        let mut beg = 0;
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetconstrs(
+                self.model,
+                &mut numnz,
+                &mut beg,
+                null_mut(),
+                null_mut(),
+                constr.index(),
+                1,
+            )
+        }));
+
+        let mut ind = vec![0; numnz as usize];
+        let mut val = vec![0.0; numnz as usize];
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetconstrs(
+                self.model,
+                &mut numnz,
+                &mut beg,
+                ind.as_mut_ptr(),
+                val.as_mut_ptr(),
+                constr.index(),
+                1,
+            )
+        }));
+        Ok(Zip::new((ind, val)).collect_vec())
+    }
+
+    // retrieve the nonzero coefficients of a variable as pairs of row index and value.
+    fn get_var_terms(&self, var: &Var) -> Result<Vec<(i32, f64)>> {
+        if var.index() < 0 {
+            return Err(Error::InconsitentDims);
+        }
+
+        let mut numnz = 0;
+        let mut beg = 0;
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetvars(
+                self.model,
+                &mut numnz,
+                &mut beg,
+                null_mut(),
+                null_mut(),
+                var.index(),
+                1,
+            )
+        }));
+
+        let mut ind = vec![0; numnz as usize];
+        let mut val = vec![0.0; numnz as usize];
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetvars(
+                self.model,
+                &mut numnz,
+                &mut beg,
+                ind.as_mut_ptr(),
+                val.as_mut_ptr(),
+                var.index(),
+                1,
+            )
+        }));
+        Ok(Zip::new((ind, val)).collect_vec())
+    }
+
+    // retrieve the linear and quadratic terms of a quadratic constraint.
+    fn get_qconstr_terms(
+        &self,
+        qconstr: &QConstr,
+    ) -> Result<(Vec<(i32, f64)>, Vec<(i32, i32, f64)>)> {
+        if qconstr.index() < 0 {
+            return Err(Error::InconsitentDims);
+        }
+
+        let (mut numlnz, mut numqnz) = (0, 0);
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetqconstr(
+                self.model,
+                qconstr.index(),
+                &mut numlnz,
+                null_mut(),
+                null_mut(),
+                &mut numqnz,
+                null_mut(),
+                null_mut(),
+                null_mut(),
+            )
+        }));
+
+        let mut lind = vec![0; numlnz as usize];
+        let mut lval = vec![0.0; numlnz as usize];
+        let mut qrow = vec![0; numqnz as usize];
+        let mut qcol = vec![0; numqnz as usize];
+        let mut qval = vec![0.0; numqnz as usize];
+        r#try!(self.check_apicall(unsafe {
+            ffi::GRBgetqconstr(
+                self.model,
+                qconstr.index(),
+                &mut numlnz,
+                lind.as_mut_ptr(),
+                lval.as_mut_ptr(),
+                &mut numqnz,
+                qrow.as_mut_ptr(),
+                qcol.as_mut_ptr(),
+                qval.as_mut_ptr(),
+            )
+        }));
+        Ok((
+            Zip::new((lind, lval)).collect_vec(),
+            Zip::new((qrow, qcol, qval)).collect_vec(),
+        ))
+    }
+
     /// Retrieve a single constant matrix coefficient of the model.
     pub fn get_coeff(&self, var: &Var, constr: &Constr) -> Result<f64> {
         let mut value = 0.0;
@@ -1506,8 +2076,15 @@ impl Model {
     }
 }
 
+impl EnvHolder for Model {
+    fn env_mut(&mut self) -> &mut Env {
+        &mut self.env
+    }
+}
+
 impl Drop for Model {
     fn drop(&mut self) {
+        self.interrupt.invalidate();
         unsafe { ffi::GRBfreemodel(self.model) };
         self.model = null_mut();
     }
@@ -0,0 +1,137 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use std::fmt;
+
+use super::{Model, Status};
+use crate::attr;
+use crate::error::Result;
+
+/// Summary of the last optimization
+///
+/// Values which are not available (e.g. `bound` of a continuous model, or `objective` of an
+/// infeasible model) are `None`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolveOutcome {
+    /// Status of the model
+    pub status: Status,
+    /// Objective value of the incumbent solution (`ObjVal`)
+    pub objective: Option<f64>,
+    /// Best known bound of the objective (`ObjBound`)
+    pub bound: Option<f64>,
+    /// Relative MIP optimality gap (`MIPGap`)
+    pub gap: Option<f64>,
+    /// Wall clock time of the optimization in seconds (`Runtime`)
+    pub runtime: Option<f64>,
+    /// Number of explored branch-and-cut nodes (`NodeCount`)
+    pub node_count: Option<f64>,
+    /// Number of simplex iterations (`IterCount`)
+    pub iter_count: Option<f64>,
+    /// Number of barrier iterations (`BarIterCount`)
+    pub bar_iter_count: Option<i32>,
+    /// Number of solutions found (`SolCount`)
+    pub sol_count: Option<i32>,
+    /// Reason why a termination policy stopped the optimization (see
+    /// [`Model::optimize_with_policy()`](struct.Model.html#method.optimize_with_policy))
+    pub stop_reason: Option<String>,
+}
+
+struct Value<T>(Option<T>);
+
+impl<T: fmt::Display> fmt::Display for Value<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(ref value) => write!(f, "{}", value),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+impl fmt::Display for SolveOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        r#try!(write!(
+            f,
+            "status: {:?}, objective: {}, bound: {}, gap: {}, runtime: {}s, nodes: {}, \
+             simplex iterations: {}, barrier iterations: {}, solutions: {}",
+            self.status,
+            Value(self.objective),
+            Value(self.bound),
+            Value(self.gap),
+            Value(self.runtime),
+            Value(self.node_count),
+            Value(self.iter_count),
+            Value(self.bar_iter_count),
+            Value(self.sol_count)
+        ));
+        if let Some(ref reason) = self.stop_reason {
+            r#try!(write!(f, ", stopped by: {}", reason));
+        }
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Optimize the model and summarize the result.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let outcome = model.optimize_report()?;
+    /// println!("{}", outcome);
+    /// if outcome.status.has_solution() {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn optimize_report(&mut self) -> Result<SolveOutcome> {
+        r#try!(self.optimize());
+        self.outcome()
+    }
+
+    /// Summarize the result of the last optimization.
+    pub fn outcome(&self) -> Result<SolveOutcome> {
+        Ok(SolveOutcome {
+            status: r#try!(self.status()),
+            objective: self.get(attr::ObjVal).ok(),
+            bound: self.get(attr::ObjBound).ok(),
+            gap: self.get(attr::MIPGap).ok(),
+            runtime: self.get(attr::Runtime).ok(),
+            node_count: self.get(attr::NodeCount).ok(),
+            iter_count: self.get(attr::IterCount).ok(),
+            bar_iter_count: self.get(attr::BarIterCount).ok(),
+            sol_count: self.get(attr::SolCount).ok(),
+            stop_reason: None,
+        })
+    }
+}
+
+#[test]
+fn outcome_should_display_missing_values() {
+    let outcome = SolveOutcome {
+        status: Status::Infeasible,
+        objective: None,
+        bound: None,
+        gap: None,
+        runtime: Some(0.5),
+        node_count: None,
+        iter_count: Some(3.0),
+        bar_iter_count: Some(0),
+        sol_count: Some(0),
+        stop_reason: None,
+    };
+    assert_eq!(
+        outcome.to_string(),
+        "status: Infeasible, objective: -, bound: -, gap: -, runtime: 0.5s, nodes: -, \
+         simplex iterations: 3, barrier iterations: 0, solutions: 0"
+    );
+
+    let outcome = SolveOutcome {
+        status: Status::Interrupted,
+        stop_reason: Some("cancelled".to_owned()),
+        ..outcome
+    };
+    assert!(outcome
+        .to_string()
+        .ends_with("solutions: 0, stopped by: cancelled"));
+}
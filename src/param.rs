@@ -11,15 +11,27 @@ pub mod exports {
     pub use self::DoubleParam::*;
     pub use self::IntParam::*;
     pub use self::StringParam::*;
+
+    pub use crate::parameter::typed;
+    pub use crate::parameter::value;
 }
 use self::exports::*;
 
+#[path = "param_typed.rs"]
+pub mod typed;
+#[path = "param_value.rs"]
+pub mod value;
+
+use crate::error::{Error, Result};
 use crate::ffi;
 use crate::util;
+use std::collections::btree_map::{self, BTreeMap};
 use std::ffi::CString;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
 
 pub trait Param: Sized + Into<CString> {
-    type Out;
+    type Out: Clone;
     type Buf: util::Init + util::Into<Self::Out> + util::AsRawPtr<Self::RawFrom>;
     type RawFrom;
     type RawTo: util::FromRaw<Self::Out>;
@@ -37,6 +49,15 @@ pub trait Param: Sized + Into<CString> {
         paramname: ffi::c_str,
         value: Self::RawTo,
     ) -> ffi::c_int;
+
+    #[doc(hidden)]
+    fn insert_into(self, set: &mut ParamSet, value: Self::Out) -> Option<Self::Out>;
+
+    #[doc(hidden)]
+    fn lookup(self, set: &ParamSet) -> Option<Self::Out>;
+
+    #[doc(hidden)]
+    fn remove_from(self, set: &mut ParamSet) -> Option<Self::Out>;
 }
 
 impl Param for IntParam {
@@ -60,6 +81,18 @@ impl Param for IntParam {
     ) -> ffi::c_int {
         ffi::GRBsetintparam(env, paramname, value)
     }
+
+    fn insert_into(self, set: &mut ParamSet, value: Self::Out) -> Option<Self::Out> {
+        set.ints.insert(self, value)
+    }
+
+    fn lookup(self, set: &ParamSet) -> Option<Self::Out> {
+        set.ints.get(&self).cloned()
+    }
+
+    fn remove_from(self, set: &mut ParamSet) -> Option<Self::Out> {
+        set.ints.remove(&self)
+    }
 }
 
 impl Param for DoubleParam {
@@ -83,6 +116,18 @@ impl Param for DoubleParam {
     ) -> ffi::c_int {
         ffi::GRBsetdblparam(env, paramname, value)
     }
+
+    fn insert_into(self, set: &mut ParamSet, value: Self::Out) -> Option<Self::Out> {
+        set.doubles.insert(self, value)
+    }
+
+    fn lookup(self, set: &ParamSet) -> Option<Self::Out> {
+        set.doubles.get(&self).cloned()
+    }
+
+    fn remove_from(self, set: &mut ParamSet) -> Option<Self::Out> {
+        set.doubles.remove(&self)
+    }
 }
 
 impl Param for StringParam {
@@ -106,4 +151,523 @@ impl Param for StringParam {
     ) -> ffi::c_int {
         ffi::GRBsetstrparam(env, paramname, value)
     }
+
+    fn insert_into(self, set: &mut ParamSet, value: Self::Out) -> Option<Self::Out> {
+        set.strings.insert(self, value)
+    }
+
+    fn lookup(self, set: &ParamSet) -> Option<Self::Out> {
+        set.strings.get(&self).cloned()
+    }
+
+    fn remove_from(self, set: &mut ParamSet) -> Option<Self::Out> {
+        set.strings.remove(&self)
+    }
+}
+
+/// An owned set of parameter values
+///
+/// A set can be captured from an environment (see
+/// [`Env::snapshot_params()`](struct.Env.html#method.snapshot_params)), read from or written to
+/// a parameter (`.prm`) file, and applied to an environment at once
+/// ([`Env::apply()`](struct.Env.html#method.apply)). With the `serde` feature, it is
+/// (de)serialized as a map from parameter names to values.
+///
+/// # Example
+/// ```ignore
+/// let mut params = ParamSet::new();
+/// params.insert(param::MIPFocus, 1);
+/// params.insert(param::TimeLimit, 60.0);
+/// env.apply(&params)?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParamSet {
+    ints: BTreeMap<IntParam, i32>,
+    doubles: BTreeMap<DoubleParam, f64>,
+    strings: BTreeMap<StringParam, String>,
+}
+
+impl ParamSet {
+    /// Create an empty set.
+    pub fn new() -> ParamSet {
+        ParamSet::default()
+    }
+
+    /// Insert the value of a parameter, returning the previous one if exists.
+    pub fn insert<P: Param>(&mut self, param: P, value: P::Out) -> Option<P::Out> {
+        param.insert_into(self, value)
+    }
+
+    /// Insert the value of a parameter (builder style).
+    pub fn with<P: Param>(mut self, param: P, value: P::Out) -> Self {
+        self.insert(param, value);
+        self
+    }
+
+    /// Query the value of a parameter.
+    pub fn get<P: Param>(&self, param: P) -> Option<P::Out> {
+        param.lookup(self)
+    }
+
+    /// Remove the value of a parameter from the set.
+    pub fn remove<P: Param>(&mut self, param: P) -> Option<P::Out> {
+        param.remove_from(self)
+    }
+
+    /// Number of the parameters in the set.
+    pub fn len(&self) -> usize {
+        self.ints.len() + self.doubles.len() + self.strings.len()
+    }
+
+    /// Returns `true` if the set contains no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retrieve an iterator of the integer parameters in the set.
+    pub fn ints(&self) -> btree_map::Iter<IntParam, i32> {
+        self.ints.iter()
+    }
+
+    /// Retrieve an iterator of the double parameters in the set.
+    pub fn doubles(&self) -> btree_map::Iter<DoubleParam, f64> {
+        self.doubles.iter()
+    }
+
+    /// Retrieve an iterator of the string parameters in the set.
+    pub fn strings(&self) -> btree_map::Iter<StringParam, String> {
+        self.strings.iter()
+    }
+
+    /// Retrieve the parameters whose values are different from (or missing in) `base`.
+    pub fn diff(&self, base: &ParamSet) -> ParamSet {
+        ParamSet {
+            ints: diff_map(&self.ints, &base.ints),
+            doubles: diff_map(&self.doubles, &base.doubles),
+            strings: diff_map(&self.strings, &base.strings),
+        }
+    }
+
+    // parameters which are also contained in `keys`.
+    pub(crate) fn restrict(&self, keys: &ParamSet) -> ParamSet {
+        ParamSet {
+            ints: restrict_map(&self.ints, &keys.ints),
+            doubles: restrict_map(&self.doubles, &keys.doubles),
+            strings: restrict_map(&self.strings, &keys.strings),
+        }
+    }
+
+    /// Read a set of parameter values from a parameter (`.prm`) file.
+    pub fn read_from(filename: &str) -> Result<ParamSet> {
+        let file = r#try!(File::open(filename));
+        ParamSet::parse(BufReader::new(file))
+    }
+
+    /// Write the set to a file in the parameter (`.prm`) format.
+    pub fn write(&self, filename: &str) -> Result<()> {
+        let file = r#try!(File::create(filename));
+        let mut writer = BufWriter::new(file);
+        r#try!(self.write_to(&mut writer));
+        r#try!(writer.flush());
+        Ok(())
+    }
+
+    /// Parse a set of parameter values in the parameter (`.prm`) format.
+    ///
+    /// Each line consists of a parameter name (case insensitive) and its value, separated by
+    /// whitespaces. Lines starting with `#` are comments. Parameters unknown to this version of
+    /// the crate are skipped, as Gurobi does.
+    pub fn parse<R: BufRead>(reader: R) -> Result<ParamSet> {
+        let mut params = ParamSet::new();
+        for (lineno, line) in reader.lines().enumerate() {
+            let line = r#try!(line);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(2, char::is_whitespace);
+            let name = fields.next().unwrap();
+            let value = fields.next().unwrap_or("").trim();
+            let invalid = || {
+                Error::Parse(format!(
+                    "line {}: invalid value `{}` for parameter {}",
+                    lineno + 1,
+                    value,
+                    name
+                ))
+            };
+            match find_param(name) {
+                Some(AnyParam::Int(param)) => {
+                    params.insert(param, r#try!(value.parse::<i32>().map_err(|_| invalid())));
+                }
+                Some(AnyParam::Double(param)) => {
+                    params.insert(param, r#try!(value.parse::<f64>().map_err(|_| invalid())));
+                }
+                Some(AnyParam::String(param)) => {
+                    params.insert(param, value.to_owned());
+                }
+                None => {}
+            }
+        }
+        Ok(params)
+    }
+
+    /// Write the set in the parameter (`.prm`) format.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        for (param, value) in self.ints.iter() {
+            r#try!(writeln!(writer, "{:?}  {}", param, value));
+        }
+        for (param, value) in self.doubles.iter() {
+            r#try!(writeln!(writer, "{:?}  {:?}", param, value));
+        }
+        for (param, value) in self.strings.iter() {
+            r#try!(writeln!(writer, "{:?}  {}", param, value));
+        }
+        Ok(())
+    }
+}
+
+impl<'a, P: Param + Copy> From<&'a [(P, P::Out)]> for ParamSet {
+    fn from(values: &'a [(P, P::Out)]) -> ParamSet {
+        let mut params = ParamSet::new();
+        for &(param, ref value) in values.iter() {
+            params.insert(param, value.clone());
+        }
+        params
+    }
+}
+
+impl<'a> From<&'a ParamSet> for ParamSet {
+    fn from(params: &'a ParamSet) -> ParamSet {
+        params.clone()
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum AnyParam {
+    Int(IntParam),
+    Double(DoubleParam),
+    String(StringParam),
+}
+
+// find a parameter by its name, ignoring case.
+fn find_param(name: &str) -> Option<AnyParam> {
+    let matches = |param: &dyn std::fmt::Debug| format!("{:?}", param).eq_ignore_ascii_case(name);
+    INT_PARAMS
+        .iter()
+        .find(|&p| matches(p))
+        .map(|&p| AnyParam::Int(p))
+        .or_else(|| {
+            DOUBLE_PARAMS
+                .iter()
+                .find(|&p| matches(p))
+                .map(|&p| AnyParam::Double(p))
+        })
+        .or_else(|| {
+            STRING_PARAMS
+                .iter()
+                .find(|&p| matches(p))
+                .map(|&p| AnyParam::String(p))
+        })
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParamSet {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> ::std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = r#try!(serializer.serialize_map(Some(self.len())));
+        for (param, value) in self.ints.iter() {
+            r#try!(map.serialize_entry(&format!("{:?}", param), value));
+        }
+        for (param, value) in self.doubles.iter() {
+            r#try!(map.serialize_entry(&format!("{:?}", param), value));
+        }
+        for (param, value) in self.strings.iter() {
+            r#try!(map.serialize_entry(&format!("{:?}", param), value));
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum RawValue {
+    Int(i64),
+    Double(f64),
+    String(String),
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ParamSet {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> ::std::result::Result<ParamSet, D::Error> {
+        use serde::de::Error as DeError;
+        use std::convert::TryFrom;
+        let raw: BTreeMap<String, RawValue> = r#try!(serde::Deserialize::deserialize(deserializer));
+        let mut params = ParamSet::new();
+        for (name, value) in raw.into_iter() {
+            match (find_param(&name), value) {
+                (Some(AnyParam::Int(param)), RawValue::Int(v)) => {
+                    let v = r#try!(i32::try_from(v).map_err(|_| {
+                        D::Error::custom(format!("value {} out of range for parameter {}", v, name))
+                    }));
+                    params.insert(param, v);
+                }
+                (Some(AnyParam::Double(param)), RawValue::Int(v)) => {
+                    params.insert(param, v as f64);
+                }
+                (Some(AnyParam::Double(param)), RawValue::Double(v)) => {
+                    params.insert(param, v);
+                }
+                (Some(AnyParam::String(param)), RawValue::String(v)) => {
+                    params.insert(param, v);
+                }
+                (Some(_), _) => {
+                    return Err(D::Error::custom(format!(
+                        "invalid value for parameter {}",
+                        name
+                    )))
+                }
+                // skipped as in `ParamSet::parse()`.
+                (None, _) => {}
+            }
+        }
+        Ok(params)
+    }
+}
+
+fn diff_map<K: Ord + Copy, V: PartialEq + Clone>(
+    map: &BTreeMap<K, V>,
+    base: &BTreeMap<K, V>,
+) -> BTreeMap<K, V> {
+    map.iter()
+        .filter(|&(k, v)| base.get(k) != Some(v))
+        .map(|(k, v)| (*k, v.clone()))
+        .collect()
+}
+
+fn restrict_map<K: Ord + Copy, V: Clone>(
+    map: &BTreeMap<K, V>,
+    keys: &BTreeMap<K, V>,
+) -> BTreeMap<K, V> {
+    map.iter()
+        .filter(|&(k, _)| keys.contains_key(k))
+        .map(|(k, v)| (*k, v.clone()))
+        .collect()
+}
+
+// all parameters defined in gurobi-sys.
+pub(crate) const INT_PARAMS: &[IntParam] = &[
+    IntParam::SolutionLimit,
+    IntParam::Method,
+    IntParam::ScaleFlag,
+    IntParam::SimplexPricing,
+    IntParam::Quad,
+    IntParam::NormAdjust,
+    IntParam::Sifting,
+    IntParam::SiftMethod,
+    IntParam::SubMIPNodes,
+    IntParam::VarBranch,
+    IntParam::Cuts,
+    IntParam::CliqueCuts,
+    IntParam::CoverCuts,
+    IntParam::FlowCoverCuts,
+    IntParam::FlowPathCuts,
+    IntParam::GUBCoverCuts,
+    IntParam::ImpliedCuts,
+    IntParam::MIPSepCuts,
+    IntParam::MIRCuts,
+    IntParam::ModKCuts,
+    IntParam::ZeroHalfCuts,
+    IntParam::NetworkCuts,
+    IntParam::SubMIPCuts,
+    IntParam::CutAggPasses,
+    IntParam::CutPasses,
+    IntParam::GomoryPasses,
+    IntParam::NodeMethod,
+    IntParam::Presolve,
+    IntParam::Aggregate,
+    IntParam::IISMethod,
+    IntParam::PreCrush,
+    IntParam::PreDepRow,
+    IntParam::PrePasses,
+    IntParam::DisplayInterval,
+    IntParam::OutputFlag,
+    IntParam::Threads,
+    IntParam::BarIterLimit,
+    IntParam::Crossover,
+    IntParam::CrossoverBasis,
+    IntParam::BarCorrectors,
+    IntParam::BarOrder,
+    IntParam::PumpPasses,
+    IntParam::RINS,
+    IntParam::Symmetry,
+    IntParam::MIPFocus,
+    IntParam::NumericFocus,
+    IntParam::AggFill,
+    IntParam::PreDual,
+    IntParam::SolutionNumber,
+    IntParam::MinRelNodes,
+    IntParam::ZeroObjNodes,
+    IntParam::BranchDir,
+    IntParam::InfUnbdInfo,
+    IntParam::DualReductions,
+    IntParam::BarHomogeneous,
+    IntParam::PreQLinearize,
+    IntParam::MIQCPMethod,
+    IntParam::QCPDual,
+    IntParam::LogToConsole,
+    IntParam::PreSparsify,
+    IntParam::PreMIQCPForm,
+    IntParam::Seed,
+    IntParam::ConcurrentMIP,
+    IntParam::ConcurrentJobs,
+    IntParam::DistributedMIPJobs,
+    IntParam::LazyConstraints,
+    IntParam::TuneResults,
+    IntParam::TuneTrials,
+    IntParam::TuneOutput,
+    IntParam::TuneJobs,
+    IntParam::Disconnected,
+    IntParam::NoRelHeuristic,
+    IntParam::UpdateMode,
+    IntParam::WorkerPort,
+    IntParam::Record,
+];
+
+pub(crate) const DOUBLE_PARAMS: &[DoubleParam] = &[
+    DoubleParam::Cutoff,
+    DoubleParam::IterationLimit,
+    DoubleParam::NodeLimit,
+    DoubleParam::TimeLimit,
+    DoubleParam::FeasibilityTol,
+    DoubleParam::IntFeasTol,
+    DoubleParam::MarkowitzTol,
+    DoubleParam::MIPGap,
+    DoubleParam::MIPGapAbs,
+    DoubleParam::OptimalityTol,
+    DoubleParam::PerturbValue,
+    DoubleParam::Heuristics,
+    DoubleParam::ObjScale,
+    DoubleParam::NodefileStart,
+    DoubleParam::BarConvTol,
+    DoubleParam::BarQCPConvTol,
+    DoubleParam::PSDTol,
+    DoubleParam::ImproveStartGap,
+    DoubleParam::ImproveStartNodes,
+    DoubleParam::ImproveStartTime,
+    DoubleParam::FeasRelaxBigM,
+    DoubleParam::TuneTimeLimit,
+    DoubleParam::PreSOS1BigM,
+    DoubleParam::PreSOS2BigM,
+];
+
+pub(crate) const STRING_PARAMS: &[StringParam] = &[
+    StringParam::LogFile,
+    StringParam::NodefileDir,
+    StringParam::ResultFile,
+    StringParam::WorkerPool,
+    StringParam::WorkerPassword,
+    StringParam::Dummy,
+];
+
+#[test]
+fn param_set_should_hold_typed_values() {
+    let mut params = ParamSet::new();
+    assert!(params.insert(MIPFocus, 1).is_none());
+    assert_eq!(params.insert(MIPFocus, 2), Some(1));
+    params.insert(TimeLimit, 10.0);
+    params.insert(LogFile, "gurobi.log".to_owned());
+    assert_eq!(params.len(), 3);
+    assert_eq!(params.get(MIPFocus), Some(2));
+    assert_eq!(params.get(Threads), None);
+
+    let mut base = params.clone();
+    base.insert(TimeLimit, 20.0);
+    base.remove(LogFile);
+    let diff = params.diff(&base);
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff.get(TimeLimit), Some(10.0));
+    assert_eq!(diff.get(LogFile), Some("gurobi.log".to_owned()));
+
+    let restricted = base.restrict(&diff);
+    assert_eq!(restricted.len(), 1);
+    assert_eq!(restricted.get(TimeLimit), Some(20.0));
+}
+
+#[test]
+fn param_file_roundtrip() {
+    let mut params = ParamSet::new();
+    params.insert(MIPFocus, 1);
+    params.insert(TimeLimit, 1e100);
+    params.insert(MIPGap, 0.005);
+    params.insert(NodefileDir, "/tmp/nodes".to_owned());
+
+    let mut buf = Vec::new();
+    params.write_to(&mut buf).unwrap();
+    assert_eq!(ParamSet::parse(&buf[..]).unwrap(), params);
+
+    let text = "# Parameter settings\nmipfocus 1\n\nTimeLimit  1e+100\nMIPGap 5e-3\nNodefileDir /tmp/nodes\n";
+    assert_eq!(ParamSet::parse(text.as_bytes()).unwrap(), params);
+
+    assert!(ParamSet::parse(&b"NoSuchParam 1\n"[..]).unwrap().is_empty());
+    assert!(ParamSet::parse(&b"MIPFocus one\n"[..]).is_err());
+}
+
+#[test]
+fn typed_params_should_share_raw_values() {
+    let mut params = ParamSet::new();
+    params.insert(typed::Method, value::Method::Barrier);
+    params.insert(typed::Cuts, value::CutLevel::Aggressive);
+    params.insert(MIPFocus, 7);
+    assert_eq!(params.get(Method), Some(2));
+    assert_eq!(params.get(Cuts), Some(2));
+    assert_eq!(params.get(typed::Method), Some(value::Method::Barrier));
+    assert_eq!(params.get(typed::MIPFocus), Some(value::MipFocus::Other(7)));
+    assert_eq!(
+        params.insert(typed::MIPFocus, value::MipFocus::Optimality),
+        Some(value::MipFocus::Other(7))
+    );
+    assert_eq!(params.get(MIPFocus), Some(2));
+    assert_eq!(i32::from(value::MipFocus::Other(7)), 7);
+    assert_eq!(
+        CString::from(typed::MIPFocus),
+        CString::new("MIPFocus").unwrap()
+    );
+}
+
+#[test]
+fn param_file_should_skip_unknown_parameters() {
+    let text = "MIPFocus 1\nNoSuchParam 3\nTimeLimit 60\n";
+    let params = ParamSet::parse(text.as_bytes()).unwrap();
+    assert_eq!(params.len(), 2);
+    assert_eq!(params.get(TimeLimit), Some(60.0));
+    assert!(ParamSet::parse("MIPFocus x\n".as_bytes()).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn param_set_serde_roundtrip() {
+    let params = ParamSet::new()
+        .with(MIPFocus, 1)
+        .with(TimeLimit, 60.0)
+        .with(LogFile, "gurobi.log".to_owned());
+    let json = serde_json::to_string(&params).unwrap();
+    assert_eq!(
+        json,
+        r#"{"MIPFocus":1,"TimeLimit":60.0,"LogFile":"gurobi.log"}"#
+    );
+    assert_eq!(serde_json::from_str::<ParamSet>(&json).unwrap(), params);
+
+    let params: ParamSet = serde_json::from_str(r#"{"timelimit":60,"Unknown":1}"#).unwrap();
+    assert_eq!(params.get(TimeLimit), Some(60.0));
+    assert_eq!(params.len(), 1);
+    assert!(serde_json::from_str::<ParamSet>(r#"{"MIPFocus":4294967296}"#).is_err());
+    assert!(serde_json::from_str::<ParamSet>(r#"{"MIPFocus":"x"}"#).is_err());
 }
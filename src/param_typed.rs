@@ -0,0 +1,133 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+//! Categorical parameters with typed values
+//!
+//! Each parameter in this module shares its name and storage with the corresponding variant of
+//! `IntParam`, but only takes a value of its own enum in
+//! [`param::value`](../value/index.html). The raw `IntParam` variants still take an arbitrary
+//! `i32`, and values unknown to the enums are kept as `Other`.
+//!
+//! # Example
+//! ```ignore
+//! use gurobi::param::{typed, value};
+//! env.set(typed::Method, value::Method::Barrier)?;
+//! env.set(typed::MIPFocus, value::MipFocus::Optimality)?;
+//! assert_eq!(env.get(param::Method)?, 2);
+//! ```
+//!
+//! A value of another parameter is rejected at compile time:
+//!
+//! ```compile_fail
+//! # use gurobi::param::{typed, value};
+//! # fn set(env: &mut gurobi::Env) -> gurobi::Result<()> {
+//! env.set(typed::Presolve, value::Method::Barrier)
+//! # }
+//! ```
+//!
+//! ```compile_fail
+//! # use gurobi::param::{self, value};
+//! # fn set(env: &mut gurobi::Env) -> gurobi::Result<()> {
+//! env.set(param::Method, value::CutLevel::Aggressive)
+//! # }
+//! ```
+
+use super::{Param, ParamSet};
+use crate::ffi;
+use crate::ffi::IntParam;
+use crate::param::value;
+use std::ffi::CString;
+
+macro_rules! typed_params {
+    ($($(#[$attr:meta])* $name:ident => $value:ident,)*) => {$(
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone)]
+        pub struct $name;
+
+        impl From<$name> for CString {
+            fn from(_: $name) -> CString {
+                IntParam::$name.into()
+            }
+        }
+
+        impl Param for $name {
+            type Out = value::$value;
+            type Buf = ffi::c_int;
+            type RawFrom = *mut ffi::c_int;
+            type RawTo = ffi::c_int;
+
+            unsafe fn get_param(
+                env: *mut ffi::GRBenv,
+                paramname: ffi::c_str,
+                val: *mut ffi::c_int,
+            ) -> ffi::c_int {
+                IntParam::get_param(env, paramname, val)
+            }
+
+            unsafe fn set_param(
+                env: *mut ffi::GRBenv,
+                paramname: ffi::c_str,
+                val: ffi::c_int,
+            ) -> ffi::c_int {
+                IntParam::set_param(env, paramname, val)
+            }
+
+            fn insert_into(self, set: &mut ParamSet, val: value::$value) -> Option<value::$value> {
+                IntParam::$name.insert_into(set, val.into()).map(value::$value::from)
+            }
+
+            fn lookup(self, set: &ParamSet) -> Option<value::$value> {
+                IntParam::$name.lookup(set).map(value::$value::from)
+            }
+
+            fn remove_from(self, set: &mut ParamSet) -> Option<value::$value> {
+                IntParam::$name.remove_from(set).map(value::$value::from)
+            }
+        }
+    )*};
+}
+
+typed_params! {
+    /// Algorithm used to solve continuous models
+    Method => Method,
+    /// Algorithm used for MIP node relaxations
+    NodeMethod => NodeMethod,
+    /// Level of presolve
+    Presolve => Presolve,
+    /// High-level solution strategy of MIP
+    MIPFocus => MipFocus,
+    /// Strategy of the barrier crossover
+    Crossover => Crossover,
+    /// Branch variable selection strategy
+    VarBranch => VarBranch,
+    /// Simplex pricing strategy
+    SimplexPricing => SimplexPricing,
+    /// Global cut generation level
+    Cuts => CutLevel,
+    /// Clique cut generation
+    CliqueCuts => CutLevel,
+    /// Cover cut generation
+    CoverCuts => CutLevel,
+    /// Flow cover cut generation
+    FlowCoverCuts => CutLevel,
+    /// Flow path cut generation
+    FlowPathCuts => CutLevel,
+    /// GUB cover cut generation
+    GUBCoverCuts => CutLevel,
+    /// Implied bound cut generation
+    ImpliedCuts => CutLevel,
+    /// MIP separation cut generation
+    MIPSepCuts => CutLevel,
+    /// MIR cut generation
+    MIRCuts => CutLevel,
+    /// Mod-k cut generation
+    ModKCuts => CutLevel,
+    /// Zero-half cut generation
+    ZeroHalfCuts => CutLevel,
+    /// Network cut generation
+    NetworkCuts => CutLevel,
+    /// Sub-MIP cut generation
+    SubMIPCuts => CutLevel,
+}
@@ -0,0 +1,194 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+//! Values of categorical parameters
+//!
+//! Each enum is the value of the corresponding parameters in
+//! [`param::typed`](../typed/index.html). Values which are not covered by the enum, e.g. those
+//! added in a later version of Gurobi, are kept as `Other`, and the raw integer value is
+//! obtained explicitly by `i32::from()`.
+//!
+//! # Example
+//! ```ignore
+//! use gurobi::param::{typed, value};
+//! env.set(typed::Method, value::Method::Barrier)?;
+//! let focus: value::MipFocus = env.get(typed::MIPFocus)?;
+//! ```
+
+use crate::ffi;
+use crate::util;
+
+macro_rules! param_values {
+    ($(#[$attr:meta])* $name:ident { $($(#[$vattr:meta])* $variant:ident = $val:expr,)* }) => {
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            $($(#[$vattr])* $variant,)*
+            /// A value which is not covered by the other variants
+            Other(i32),
+        }
+
+        impl From<$name> for i32 {
+            fn from(val: $name) -> i32 {
+                match val {
+                    $($name::$variant => $val,)*
+                    $name::Other(val) => val,
+                }
+            }
+        }
+
+        impl From<i32> for $name {
+            fn from(val: i32) -> $name {
+                match val {
+                    $(x if x == $val => $name::$variant,)*
+                    _ => $name::Other(val),
+                }
+            }
+        }
+
+        impl util::Into<$name> for ffi::c_int {
+            fn into(self) -> $name {
+                $name::from(self)
+            }
+        }
+
+        impl util::FromRaw<$name> for ffi::c_int {
+            fn from(val: $name) -> ffi::c_int {
+                val.into()
+            }
+        }
+    };
+}
+
+param_values! {
+    /// Algorithm used to solve continuous models (`Method`)
+    Method {
+        /// Chosen automatically
+        Automatic = -1,
+        /// Primal simplex
+        PrimalSimplex = 0,
+        /// Dual simplex
+        DualSimplex = 1,
+        /// Barrier
+        Barrier = 2,
+        /// Concurrent
+        Concurrent = 3,
+        /// Deterministic concurrent
+        DeterministicConcurrent = 4,
+        /// Deterministic concurrent simplex
+        DeterministicConcurrentSimplex = 5,
+    }
+}
+
+param_values! {
+    /// Algorithm used for MIP node relaxations (`NodeMethod`)
+    NodeMethod {
+        /// Chosen automatically
+        Automatic = -1,
+        /// Primal simplex
+        PrimalSimplex = 0,
+        /// Dual simplex
+        DualSimplex = 1,
+        /// Barrier
+        Barrier = 2,
+    }
+}
+
+param_values! {
+    /// Level of presolve (`Presolve`)
+    Presolve {
+        /// Chosen automatically
+        Automatic = -1,
+        /// Presolve is turned off
+        Off = 0,
+        /// Conservative
+        Conservative = 1,
+        /// Aggressive
+        Aggressive = 2,
+    }
+}
+
+param_values! {
+    /// High-level solution strategy of MIP (`MIPFocus`)
+    MipFocus {
+        /// Balance between finding feasible solutions and proving optimality
+        Balanced = 0,
+        /// Focus on finding feasible solutions
+        Feasibility = 1,
+        /// Focus on proving optimality
+        Optimality = 2,
+        /// Focus on moving the objective bound
+        Bound = 3,
+    }
+}
+
+param_values! {
+    /// Strategy of the barrier crossover (`Crossover`)
+    Crossover {
+        /// Chosen automatically
+        Automatic = -1,
+        /// Crossover is turned off
+        Off = 0,
+        /// Push dual variables first, then primal variables, and finish with primal simplex
+        DualFirstPrimalFinish = 1,
+        /// Push dual variables first, then primal variables, and finish with dual simplex
+        DualFirstDualFinish = 2,
+        /// Push primal variables first, then dual variables, and finish with primal simplex
+        PrimalFirstPrimalFinish = 3,
+        /// Push primal variables first, then dual variables, and finish with dual simplex
+        PrimalFirstDualFinish = 4,
+    }
+}
+
+param_values! {
+    /// Branch variable selection strategy (`VarBranch`)
+    VarBranch {
+        /// Chosen automatically
+        Automatic = -1,
+        /// Pseudo reduced cost branching
+        PseudoReducedCost = 0,
+        /// Pseudo shadow price branching
+        PseudoShadowPrice = 1,
+        /// Maximum infeasibility branching
+        MaxInfeasibility = 2,
+        /// Strong branching
+        StrongBranching = 3,
+    }
+}
+
+param_values! {
+    /// Simplex pricing strategy (`SimplexPricing`)
+    SimplexPricing {
+        /// Chosen automatically
+        Automatic = -1,
+        /// Partial pricing
+        Partial = 0,
+        /// Steepest edge
+        SteepestEdge = 1,
+        /// Devex
+        Devex = 2,
+        /// Quick-start steepest edge
+        QuickStartSteepestEdge = 3,
+    }
+}
+
+param_values! {
+    /// Aggressiveness of cut generation (`Cuts` and the parameters of each cut type)
+    ///
+    /// Some cut types accept only up to `Aggressive`.
+    CutLevel {
+        /// Chosen automatically
+        Automatic = -1,
+        /// Cuts are turned off
+        Off = 0,
+        /// Moderate
+        Moderate = 1,
+        /// Aggressive
+        Aggressive = 2,
+        /// Very aggressive
+        VeryAggressive = 3,
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use itertools::{Itertools, Zip};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::slice::Iter;
+
+use super::{Constr, ConstrSense, Model, Status, Var};
+use crate::attr;
+use crate::error::{Error, Result, DATA_NOT_AVAILABLE, NOT_FOR_MIP};
+use crate::INFINITY;
+
+/// Sensitivity information of a variable
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VarSensitivity {
+    /// Proxy object of the variable
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub var: Var,
+    /// Name of the variable
+    pub name: String,
+    /// Value in the optimal solution (`X`)
+    pub value: f64,
+    /// Reduced cost (`RC`)
+    pub reduced_cost: f64,
+    /// Objective coefficient (`Obj`)
+    pub obj: f64,
+    /// Objective coefficient ranging (`SAObjLow`, `SAObjUp`)
+    pub obj_range: (f64, f64),
+    /// Lower bound ranging (`SALBLow`, `SALBUp`)
+    pub lb_range: (f64, f64),
+    /// Upper bound ranging (`SAUBLow`, `SAUBUp`)
+    pub ub_range: (f64, f64),
+}
+
+/// Sensitivity information of a linear constraint
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConstrSensitivity {
+    /// Proxy object of the constraint
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub constr: Constr,
+    /// Name of the constraint
+    pub name: String,
+    /// Sense of the constraint
+    pub sense: ConstrSense,
+    /// Right-hand side value (`RHS`)
+    pub rhs: f64,
+    /// Slack in the optimal solution (`Slack`)
+    pub slack: f64,
+    /// Dual value (`Pi`)
+    pub dual: f64,
+    /// Right-hand side ranging (`SARHSLow`, `SARHSUp`)
+    pub rhs_range: (f64, f64),
+}
+
+/// Sensitivity analysis of an optimal LP solution
+///
+/// See [`Model::sensitivity()`](struct.Model.html#method.sensitivity).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SensitivityReport {
+    vars: Vec<VarSensitivity>,
+    constrs: Vec<ConstrSensitivity>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    var_names: HashMap<String, usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    constr_names: HashMap<String, usize>,
+}
+
+// the entries are stored in the order of the model, so the position of a proxy object is tried
+// first. it is not valid anymore if the model was modified after the analysis.
+fn lookup<'a, T, F>(entries: &'a [T], index: i32, matches: F) -> Option<&'a T>
+where
+    F: Fn(&T) -> bool,
+{
+    if index >= 0 {
+        if let Some(entry) = entries.get(index as usize).filter(|&e| matches(e)) {
+            return Some(entry);
+        }
+    }
+    entries.iter().find(|&e| matches(e))
+}
+
+impl SensitivityReport {
+    fn new(vars: Vec<VarSensitivity>, constrs: Vec<ConstrSensitivity>) -> SensitivityReport {
+        let var_names = vars
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.name.clone(), i))
+            .collect();
+        let constr_names = constrs
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.name.clone(), i))
+            .collect();
+        SensitivityReport {
+            vars,
+            constrs,
+            var_names,
+            constr_names,
+        }
+    }
+
+    /// Retrieve an iterator of the sensitivity information of the variables.
+    pub fn vars(&self) -> Iter<VarSensitivity> {
+        self.vars.iter()
+    }
+
+    /// Retrieve an iterator of the sensitivity information of the linear constraints.
+    pub fn constrs(&self) -> Iter<ConstrSensitivity> {
+        self.constrs.iter()
+    }
+
+    /// Query the sensitivity information of a variable.
+    pub fn var(&self, var: &Var) -> Option<&VarSensitivity> {
+        lookup(&self.vars, var.index(), |s| &s.var == var)
+    }
+
+    /// Query the sensitivity information of a variable by its name.
+    pub fn var_by_name(&self, name: &str) -> Option<&VarSensitivity> {
+        self.var_names.get(name).map(|&i| &self.vars[i])
+    }
+
+    /// Query the sensitivity information of a linear constraint.
+    pub fn constr(&self, constr: &Constr) -> Option<&ConstrSensitivity> {
+        lookup(&self.constrs, constr.index(), |s| &s.constr == constr)
+    }
+
+    /// Query the sensitivity information of a linear constraint by its name.
+    pub fn constr_by_name(&self, name: &str) -> Option<&ConstrSensitivity> {
+        self.constr_names.get(name).map(|&i| &self.constrs[i])
+    }
+}
+
+struct Num(f64);
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 >= INFINITY {
+            write!(f, "{:>12}", "inf")
+        } else if self.0 <= -INFINITY {
+            write!(f, "{:>12}", "-inf")
+        } else {
+            write!(f, "{:>12.6}", self.0)
+        }
+    }
+}
+
+impl fmt::Display for SensitivityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        r#try!(writeln!(
+            f,
+            "{:<16} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "Variable",
+            "Value",
+            "RedCost",
+            "Obj",
+            "ObjLow",
+            "ObjUp",
+            "LBLow",
+            "LBUp",
+            "UBLow",
+            "UBUp"
+        ));
+        for s in self.vars.iter() {
+            r#try!(writeln!(
+                f,
+                "{:<16} {} {} {} {} {} {} {} {} {}",
+                s.name,
+                Num(s.value),
+                Num(s.reduced_cost),
+                Num(s.obj),
+                Num(s.obj_range.0),
+                Num(s.obj_range.1),
+                Num(s.lb_range.0),
+                Num(s.lb_range.1),
+                Num(s.ub_range.0),
+                Num(s.ub_range.1)
+            ));
+        }
+
+        r#try!(writeln!(f));
+        r#try!(writeln!(
+            f,
+            "{:<16} {:>5} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "Constraint", "Sense", "RHS", "Slack", "Dual", "RHSLow", "RHSUp"
+        ));
+        for s in self.constrs.iter() {
+            let sense = match s.sense {
+                ConstrSense::Equal => "=",
+                ConstrSense::Less => "<=",
+                ConstrSense::Greater => ">=",
+            };
+            r#try!(writeln!(
+                f,
+                "{:<16} {:>5} {} {} {} {} {}",
+                s.name,
+                sense,
+                Num(s.rhs),
+                Num(s.slack),
+                Num(s.dual),
+                Num(s.rhs_range.0),
+                Num(s.rhs_range.1)
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Compute the sensitivity analysis of the optimal solution.
+    ///
+    /// Sensitivity information is only available for continuous models.
+    /// For a MIP, solve it first and call this method on the fixed model
+    /// (see [`fixed()`](#method.fixed)).
+    pub fn sensitivity(&self) -> Result<SensitivityReport> {
+        if r#try!(self.get(attr::IsMIP)) != 0 {
+            return Err(Error::FromAPI(
+                "Sensitivity information is not available for MIP models; \
+                 compute it on the fixed model (`Model::fixed()`) instead"
+                    .to_owned(),
+                NOT_FOR_MIP,
+            ));
+        }
+        if r#try!(self.status()) != Status::Optimal {
+            return Err(Error::FromAPI(
+                "Sensitivity information requires an optimal solution".to_owned(),
+                DATA_NOT_AVAILABLE,
+            ));
+        }
+
+        let vars = self.vars.as_slice();
+        let names = r#try!(self.get_values(attr::VarName, vars));
+        let x = r#try!(self.get_values(attr::X, vars));
+        let rc = r#try!(self.get_values(attr::RC, vars));
+        let obj = r#try!(self.get_values(attr::Obj, vars));
+        let obj_low = r#try!(self.get_values(attr::SAObjLow, vars));
+        let obj_up = r#try!(self.get_values(attr::SAObjUp, vars));
+        let lb_low = r#try!(self.get_values(attr::SALBLow, vars));
+        let lb_up = r#try!(self.get_values(attr::SALBUp, vars));
+        let ub_low = r#try!(self.get_values(attr::SAUBLow, vars));
+        let ub_up = r#try!(self.get_values(attr::SAUBUp, vars));
+
+        let vars = (0..vars.len())
+            .map(|i| VarSensitivity {
+                var: vars[i].clone(),
+                name: names[i].clone(),
+                value: x[i],
+                reduced_cost: rc[i],
+                obj: obj[i],
+                obj_range: (obj_low[i], obj_up[i]),
+                lb_range: (lb_low[i], lb_up[i]),
+                ub_range: (ub_low[i], ub_up[i]),
+            })
+            .collect_vec();
+
+        let constrs = self.constrs.as_slice();
+        let names = r#try!(self.get_values(attr::ConstrName, constrs));
+        let senses = r#try!(self.get_values(attr::Sense, constrs));
+        let rhs = r#try!(self.get_values(attr::RHS, constrs));
+        let slack = r#try!(self.get_values(attr::Slack, constrs));
+        let pi = r#try!(self.get_values(attr::Pi, constrs));
+        let rhs_low = r#try!(self.get_values(attr::SARHSLow, constrs));
+        let rhs_up = r#try!(self.get_values(attr::SARHSUp, constrs));
+
+        let mut buf = Vec::with_capacity(constrs.len());
+        for (i, (c, name, &sense)) in Zip::new((constrs, names, senses.iter())).enumerate() {
+            buf.push(ConstrSensitivity {
+                constr: c.clone(),
+                name,
+                sense: r#try!(ConstrSense::try_from(sense)),
+                rhs: rhs[i],
+                slack: slack[i],
+                dual: pi[i],
+                rhs_range: (rhs_low[i], rhs_up[i]),
+            });
+        }
+
+        Ok(SensitivityReport::new(vars, buf))
+    }
+}
+
+#[test]
+fn sensitivity_report_should_be_looked_up_and_displayed() {
+    let x = Var::new(0);
+    let y = Var::new(1);
+    let c = Constr::new(0);
+    let vars = vec![
+        VarSensitivity {
+            var: x.clone(),
+            name: "x".to_owned(),
+            value: 1.0,
+            reduced_cost: 0.0,
+            obj: 2.0,
+            obj_range: (1.5, INFINITY),
+            lb_range: (-INFINITY, 1.0),
+            ub_range: (1.0, INFINITY),
+        },
+        VarSensitivity {
+            var: y.clone(),
+            name: "y".to_owned(),
+            value: 0.0,
+            reduced_cost: 0.5,
+            obj: 1.0,
+            obj_range: (0.5, INFINITY),
+            lb_range: (-INFINITY, 2.0),
+            ub_range: (0.0, INFINITY),
+        },
+    ];
+    let constrs = vec![ConstrSensitivity {
+        constr: c.clone(),
+        name: "c".to_owned(),
+        sense: ConstrSense::Less,
+        rhs: 1.0,
+        slack: 0.0,
+        dual: -2.0,
+        rhs_range: (0.0, 3.0),
+    }];
+    let report = SensitivityReport::new(vars, constrs);
+
+    assert_eq!(report.var(&y).unwrap().name, "y");
+    assert_eq!(report.var_by_name("x").unwrap().var, x);
+    assert!(report.var(&Var::new(0)).is_none());
+    assert!(report.var_by_name("z").is_none());
+    assert_eq!(report.constr(&c).unwrap().dual, -2.0);
+    assert_eq!(report.constr_by_name("c").unwrap().constr, c);
+
+    // stale positions fall back to the search by identity.
+    let mut y2 = y.clone();
+    y2.set_index(0);
+    assert_eq!(report.var(&y).unwrap().name, "y");
+
+    let text = report.to_string();
+    let lines = text.lines().collect_vec();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with("Variable"));
+    assert_eq!(
+        lines[1],
+        concat!(
+            "x                    1.000000     0.000000     2.000000     1.500000",
+            "          inf         -inf     1.000000     1.000000          inf"
+        )
+    );
+    assert!(lines[2].starts_with("y "));
+    assert_eq!(lines[3], "");
+    assert!(lines[4].starts_with("Constraint"));
+    assert_eq!(
+        lines[5],
+        "c                   <=     1.000000     0.000000    -2.000000     0.000000     3.000000"
+    );
+}
@@ -0,0 +1,435 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use itertools::{Itertools, Zip};
+use std::collections::HashSet;
+
+use super::callback::{MipNodeCtx, MipSolCtx, ModelView};
+use super::expr::LinExpr;
+use super::handler::CallbackHandler;
+use super::{ConstrSense, Model, Status, Var};
+use crate::error::{Error, Result};
+use crate::param;
+use crate::parameter::ParamSet;
+
+/// Kind of the solution given to a separator
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SolutionKind {
+    /// A new incumbent solution (`MIPSol`)
+    Incumbent,
+    /// An optimal solution of the node relaxation (`MIPNode`)
+    NodeRelaxation,
+}
+
+/// Values of all variables in the solution given to a separator
+#[derive(Debug, Clone)]
+pub struct SolutionView {
+    kind: SolutionKind,
+    node_count: f64,
+    values: Vec<f64>,
+}
+
+impl SolutionView {
+    // retrieve the new incumbent solution.
+    pub(crate) fn incumbent(ctx: &MipSolCtx, vars: &[Var]) -> Result<SolutionView> {
+        Ok(SolutionView {
+            kind: SolutionKind::Incumbent,
+            node_count: ctx.node_count(),
+            values: r#try!(ctx.solution(vars)),
+        })
+    }
+
+    // retrieve the solution of the node relaxation.
+    pub(crate) fn node_relaxation(ctx: &MipNodeCtx, vars: &[Var]) -> Result<SolutionView> {
+        Ok(SolutionView {
+            kind: SolutionKind::NodeRelaxation,
+            node_count: ctx.node_count(),
+            values: r#try!(ctx.node_rel(vars)),
+        })
+    }
+
+    /// Kind of the solution.
+    pub fn kind(&self) -> SolutionKind {
+        self.kind
+    }
+
+    /// Number of explored nodes when the solution was found.
+    pub fn node_count(&self) -> f64 {
+        self.node_count
+    }
+
+    /// Retrieve the value of a variable.
+    ///
+    /// Returns `None` if the variable is not in the model being solved.
+    pub fn value(&self, var: &Var) -> Option<f64> {
+        self.value_at(var.index())
+    }
+
+    /// Retrieve the values of variables.
+    ///
+    /// Returns `None` if any of the variables is not in the model being solved.
+    pub fn values(&self, vars: &[Var]) -> Option<Vec<f64>> {
+        vars.iter().map(|v| self.value(v)).collect()
+    }
+
+    /// Evaluate a linear expression at the solution.
+    ///
+    /// Returns `None` if the expression contains a variable which is not in the model.
+    pub fn eval(&self, expr: &LinExpr) -> Option<f64> {
+        let (inds, coeff, offset) = expr.clone().into();
+        let mut value = offset;
+        for (&i, c) in Zip::new((inds.iter(), coeff.iter())) {
+            value += c * self.value_at(i)?;
+        }
+        Some(value)
+    }
+
+    fn value_at(&self, index: i32) -> Option<f64> {
+        if index < 0 {
+            return None;
+        }
+        self.values.get(index as usize).cloned()
+    }
+}
+
+/// Inequality returned by a separator
+#[derive(Debug, Clone)]
+pub struct Cut {
+    /// Left-hand side of the inequality
+    pub lhs: LinExpr,
+    /// Sense of the inequality
+    pub sense: ConstrSense,
+    /// Right-hand side of the inequality
+    pub rhs: f64,
+}
+
+impl Cut {
+    /// Create a new inequality.
+    pub fn new(lhs: LinExpr, sense: ConstrSense, rhs: f64) -> Cut {
+        Cut {
+            lhs,
+            sense,
+            rhs,
+        }
+    }
+
+    /// Amount of the violation of the inequality at the solution (non-positive if satisfied).
+    ///
+    /// Returns `None` if the inequality contains a variable which is not in the model.
+    pub fn violation(&self, sol: &SolutionView) -> Option<f64> {
+        let lhs = sol.eval(&self.lhs)?;
+        Some(match self.sense {
+            ConstrSense::Less => lhs - self.rhs,
+            ConstrSense::Greater => self.rhs - lhs,
+            ConstrSense::Equal => (lhs - self.rhs).abs(),
+        })
+    }
+
+    // normalized form used to detect duplicated cuts.
+    fn key(&self) -> CutKey {
+        let (inds, coeff, offset) = self.lhs.clone().into();
+        let mut terms = Zip::new((inds, coeff))
+            .filter(|&(_, c)| c != 0.0)
+            .collect_vec();
+        terms.sort_by_key(|&(i, _)| i);
+        let mut merged: Vec<(i32, u64)> = Vec::with_capacity(terms.len());
+        let mut last: Option<(i32, f64)> = None;
+        for (i, c) in terms {
+            last = match last {
+                Some((j, d)) if j == i => Some((j, d + c)),
+                Some((j, d)) => {
+                    merged.push((j, d.to_bits()));
+                    Some((i, c))
+                }
+                None => Some((i, c)),
+            };
+        }
+        if let Some((j, d)) = last {
+            merged.push((j, d.to_bits()));
+        }
+        let sense: i8 = self.sense.into();
+        CutKey {
+            terms: merged,
+            sense,
+            rhs: (self.rhs - offset).to_bits(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CutKey {
+    terms: Vec<(i32, u64)>,
+    sense: i8,
+    rhs: u64,
+}
+
+/// Separation routine of violated inequalities
+///
+/// # Example
+/// ```ignore
+/// struct SubtourElimination { /* ... */ }
+///
+/// impl Separator for SubtourElimination {
+///     fn separate(&mut self, model: ModelView, sol: &SolutionView) -> Result<Vec<Cut>> {
+///         let tours = find_subtours(&self.edges, sol);
+///         Ok(tours.into_iter().map(|tour| self.elimination(tour)).collect())
+///     }
+/// }
+///
+/// let mut separation = Separation::lazy(SubtourElimination::new(/* ... */));
+/// model.optimize_with_separation(&mut separation)?;
+/// println!("{} constraints added", separation.pool().len());
+/// ```
+pub trait Separator {
+    /// Return the inequalities violated by the solution.
+    ///
+    /// Inequalities which are not violated beyond the tolerance of the driver are ignored, so
+    /// the routine does not need to check the violations by itself.
+    fn separate(&mut self, model: ModelView, sol: &SolutionView) -> Result<Vec<Cut>>;
+}
+
+impl<F> Separator for F
+where
+    F: FnMut(ModelView, &SolutionView) -> Result<Vec<Cut>>,
+{
+    fn separate(&mut self, model: ModelView, sol: &SolutionView) -> Result<Vec<Cut>> {
+        self(model, sol)
+    }
+}
+
+/// How the inequalities are added to the model
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SeparationMode {
+    /// Lazy constraints, which are required for the feasibility of solutions. The separator is
+    /// called at each new incumbent and at each node relaxation.
+    Lazy,
+    /// User cuts, which only tighten the relaxation. The separator is called at each node
+    /// relaxation.
+    UserCut,
+}
+
+/// Statistics of a separation round
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SeparationRound {
+    /// Number of explored nodes at the round
+    pub node_count: f64,
+    /// `true` if the separator was called with an incumbent solution
+    pub incumbent: bool,
+    /// Number of inequalities returned by the separator
+    pub generated: usize,
+    /// Number of inequalities which were not violated beyond the tolerance
+    pub satisfied: usize,
+    /// Number of inequalities already in the cut pool
+    pub duplicated: usize,
+    /// Number of inequalities added to the model
+    pub added: usize,
+    /// Maximum violation of the added inequalities
+    pub max_violation: f64,
+}
+
+/// Driver which installs a separator as lazy constraints or user cuts
+///
+/// Run it by [`Model::optimize_with_separation()`](struct.Model.html#method.optimize_with_separation).
+/// The driver is also a [`CallbackHandler`](trait.CallbackHandler.html), so it can be combined
+/// with other handlers by [`CallbackChain`](struct.CallbackChain.html); then the parameter
+/// required by the mode must be set by [`install()`](#method.install).
+pub struct Separation<S: Separator> {
+    separator: S,
+    mode: SeparationMode,
+    tolerance: f64,
+    keys: HashSet<CutKey>,
+    pool: Vec<Cut>,
+    rounds: Vec<SeparationRound>,
+}
+
+impl<S: Separator> Separation<S> {
+    /// Create a driver which adds the inequalities as lazy constraints.
+    pub fn lazy(separator: S) -> Separation<S> {
+        Separation::new(separator, SeparationMode::Lazy)
+    }
+
+    /// Create a driver which adds the inequalities as user cuts.
+    pub fn user_cut(separator: S) -> Separation<S> {
+        Separation::new(separator, SeparationMode::UserCut)
+    }
+
+    /// Create a driver.
+    pub fn new(separator: S, mode: SeparationMode) -> Separation<S> {
+        Separation {
+            separator,
+            mode,
+            tolerance: 1e-6,
+            keys: HashSet::new(),
+            pool: Vec::new(),
+            rounds: Vec::new(),
+        }
+    }
+
+    /// Set the minimum violation of the inequalities to be added (default: `1e-6`).
+    pub fn tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the parameter required by the mode (`LazyConstraints` or `PreCrush`) to the model.
+    pub fn install(&self, model: &mut Model) -> Result<()> {
+        model.get_env_mut().apply(&self.params())
+    }
+
+    fn params(&self) -> ParamSet {
+        match self.mode {
+            SeparationMode::Lazy => ParamSet::new().with(param::LazyConstraints, 1),
+            SeparationMode::UserCut => ParamSet::new().with(param::PreCrush, 1),
+        }
+    }
+
+    /// Retrieve the separator.
+    pub fn separator(&self) -> &S {
+        &self.separator
+    }
+
+    /// Retrieve the inequalities added to the model, in the order they were added.
+    pub fn pool(&self) -> &[Cut] {
+        &self.pool
+    }
+
+    /// Retrieve the statistics of each round.
+    pub fn rounds(&self) -> &[SeparationRound] {
+        &self.rounds
+    }
+
+    /// Consume the driver and retrieve the separator.
+    pub fn into_inner(self) -> S {
+        self.separator
+    }
+
+    // call the separator and select the violated inequalities which are not in the pool.
+    fn separate(&mut self, model: ModelView, sol: &SolutionView) -> Result<Vec<Cut>> {
+        let cuts = r#try!(self.separator.separate(model, sol));
+        self.select(cuts, sol)
+    }
+
+    fn select(&mut self, cuts: Vec<Cut>, sol: &SolutionView) -> Result<Vec<Cut>> {
+        let mut round = SeparationRound {
+            node_count: sol.node_count(),
+            incumbent: sol.kind() == SolutionKind::Incumbent,
+            generated: cuts.len(),
+            satisfied: 0,
+            duplicated: 0,
+            added: 0,
+            max_violation: 0.0,
+        };
+
+        let mut selected = Vec::new();
+        for cut in cuts {
+            let violation = match cut.violation(sol) {
+                Some(violation) => violation,
+                None => {
+                    return Err(Error::InvalidValue(
+                        "inequality with a variable which is not in the model".to_owned(),
+                    ))
+                }
+            };
+            if violation <= self.tolerance {
+                round.satisfied += 1;
+                continue;
+            }
+            if !self.keys.insert(cut.key()) {
+                round.duplicated += 1;
+                continue;
+            }
+            round.added += 1;
+            round.max_violation = round.max_violation.max(violation);
+            self.pool.push(cut.clone());
+            selected.push(cut);
+        }
+
+        self.rounds.push(round);
+        Ok(selected)
+    }
+}
+
+impl<S: Separator> CallbackHandler for Separation<S> {
+    fn on_mip_sol(&mut self, ctx: &MipSolCtx) -> Result<()> {
+        if self.mode != SeparationMode::Lazy {
+            return Ok(());
+        }
+        let vars = ctx.model().get_vars().cloned().collect_vec();
+        let sol = r#try!(SolutionView::incumbent(ctx, &vars));
+        for cut in r#try!(self.separate(ctx.model(), &sol)) {
+            r#try!(ctx.add_lazy(cut.lhs, cut.sense, cut.rhs));
+        }
+        Ok(())
+    }
+
+    fn on_mip_node(&mut self, ctx: &MipNodeCtx) -> Result<()> {
+        if ctx.status() != Status::Optimal {
+            return Ok(());
+        }
+        let vars = ctx.model().get_vars().cloned().collect_vec();
+        let sol = r#try!(SolutionView::node_relaxation(ctx, &vars));
+        for cut in r#try!(self.separate(ctx.model(), &sol)) {
+            match self.mode {
+                SeparationMode::Lazy => r#try!(ctx.add_lazy(cut.lhs, cut.sense, cut.rhs)),
+                SeparationMode::UserCut => r#try!(ctx.add_cut(cut.lhs, cut.sense, cut.rhs)),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Optimize the model with a separation driver.
+    ///
+    /// The parameter required by the mode of the driver (`LazyConstraints` or `PreCrush`) is set
+    /// during the optimization, and restored afterwards.
+    pub fn optimize_with_separation<S: Separator>(
+        &mut self,
+        separation: &mut Separation<S>,
+    ) -> Result<()> {
+        let mut model = r#try!(self.with_params(separation.params()));
+        model.optimize_with_handler(separation)
+    }
+}
+
+#[test]
+fn separation_should_skip_satisfied_and_duplicated_cuts() {
+    let x = Var::new(0);
+    let y = Var::new(1);
+    let sol = SolutionView {
+        kind: SolutionKind::NodeRelaxation,
+        node_count: 0.0,
+        values: vec![0.5, 0.75],
+    };
+    assert_eq!(sol.eval(&(&x + &y)), Some(1.25));
+    assert_eq!(sol.value(&y), Some(0.75));
+    assert_eq!(sol.value(&Var::new(2)), None);
+    assert_eq!(sol.value(&Var::new(-1)), None);
+    assert_eq!(sol.values(&[y.clone(), x.clone()]), Some(vec![0.75, 0.5]));
+
+    let cuts = vec![
+        Cut::new(&x + &y, ConstrSense::Less, 1.0),
+        Cut::new(&y + &x, ConstrSense::Less, 1.0),
+        Cut::new(LinExpr::from(&x), ConstrSense::Less, 1.0),
+        Cut::new(LinExpr::from(&y), ConstrSense::Greater, 1.0),
+    ];
+    let separator = |_: ModelView, _: &SolutionView| -> Result<Vec<Cut>> { Ok(Vec::new()) };
+    let mut separation = Separation::user_cut(separator);
+    assert_eq!(separation.select(cuts.clone(), &sol).unwrap().len(), 2);
+    assert_eq!(separation.select(cuts, &sol).unwrap().len(), 0);
+    assert_eq!(separation.pool().len(), 2);
+
+    let round = &separation.rounds()[0];
+    assert_eq!(round.generated, 4);
+    assert_eq!(round.satisfied, 1);
+    assert_eq!(round.duplicated, 1);
+    assert_eq!(round.added, 2);
+    assert_eq!(round.max_violation, 0.25);
+    assert_eq!(separation.rounds()[1].duplicated, 3);
+
+    let unknown = Cut::new(LinExpr::from(&Var::new(5)), ConstrSense::Less, 0.0);
+    assert!(separation.select(vec![unknown], &sol).is_err());
+}
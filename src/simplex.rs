@@ -0,0 +1,246 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use crate::ffi;
+use itertools::Zip;
+
+use std::slice::Iter;
+
+use super::{Model, Status};
+use crate::attr;
+use crate::error::{Error, Result, DATA_NOT_AVAILABLE, NOT_FOR_MIP};
+
+/// Sparse vector used by the advanced simplex routines.
+///
+/// Each entry is a pair of an index and a value.
+/// For right-hand side vectors and results of `ftran()`/`btran()`/`binv_col()`, indices refer to
+/// rows of the constraint matrix.
+/// For results of `binv_row()`, indices refer to columns; the column `j >= NumVars` is the slack
+/// of the constraint `j - NumVars`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SparseVec {
+    ind: Vec<i32>,
+    val: Vec<f64>,
+}
+
+impl SparseVec {
+    /// Create an empty sparse vector.
+    pub fn new() -> SparseVec {
+        SparseVec::default()
+    }
+
+    /// Create a sparse vector from indices and values. Panics if the lengths do not match.
+    pub fn from_parts(ind: Vec<i32>, val: Vec<f64>) -> SparseVec {
+        assert_eq!(ind.len(), val.len());
+        SparseVec { ind, val }
+    }
+
+    /// Create a sparse vector from the non-zero entries of a dense vector.
+    pub fn from_dense(dense: &[f64]) -> SparseVec {
+        let mut vec = SparseVec::new();
+        for (i, &v) in dense.iter().enumerate() {
+            if v != 0.0 {
+                vec.push(i as i32, v);
+            }
+        }
+        vec
+    }
+
+    /// Append an entry to the vector.
+    pub fn push(&mut self, ind: i32, val: f64) {
+        self.ind.push(ind);
+        self.val.push(val);
+    }
+
+    /// The number of stored entries.
+    pub fn len(&self) -> usize {
+        self.ind.len()
+    }
+
+    /// Returns `true` if the vector has no stored entries.
+    pub fn is_empty(&self) -> bool {
+        self.ind.is_empty()
+    }
+
+    /// Indices of the stored entries.
+    pub fn indices(&self) -> &[i32] {
+        self.ind.as_slice()
+    }
+
+    /// Values of the stored entries.
+    pub fn values(&self) -> &[f64] {
+        self.val.as_slice()
+    }
+
+    /// Iterate over the pairs of index and value.
+    pub fn iter(&self) -> Zip<(Iter<i32>, Iter<f64>)> {
+        Zip::new((self.ind.iter(), self.val.iter()))
+    }
+
+    /// Convert into a dense vector of length `len`. Entries out of range are ignored.
+    pub fn to_dense(&self, len: usize) -> Vec<f64> {
+        let mut buf = vec![0.0; len];
+        for (&i, &v) in self.iter() {
+            if (i as usize) < len {
+                buf[i as usize] += v;
+            }
+        }
+        buf
+    }
+
+    // check that all indices are in `0..len`.
+    fn check_indices(&self, len: usize) -> Result<()> {
+        for &i in &self.ind {
+            r#try!(check_index(i, len));
+        }
+        Ok(())
+    }
+
+    fn with_capacity(len: usize) -> SparseVec {
+        SparseVec {
+            ind: vec![0; len],
+            val: vec![0.0; len],
+        }
+    }
+
+    fn as_raw(&mut self) -> ffi::GRBsvec {
+        ffi::GRBsvec {
+            len: self.ind.len() as ffi::c_int,
+            ind: self.ind.as_mut_ptr(),
+            val: self.val.as_mut_ptr(),
+        }
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.ind.truncate(len);
+        self.val.truncate(len);
+    }
+}
+
+fn check_index(i: i32, len: usize) -> Result<()> {
+    if i < 0 || i as usize >= len {
+        return Err(Error::InconsitentDims);
+    }
+    Ok(())
+}
+
+impl Model {
+    /// Retrieve the indices of the basic variables in the current optimal basis.
+    ///
+    /// The `i`-th entry is the column that is basic in row `i`.
+    /// A value `j < NumVars` refers to the variable `j`, and a value `j >= NumVars` refers to the
+    /// slack of the constraint `j - NumVars`.
+    pub fn basis_head(&self) -> Result<Vec<i32>> {
+        let rows = r#try!(self.check_lp_basis());
+        let mut bhead = vec![0; rows];
+        r#try!(self.check_apicall(unsafe { ffi::GRBgetBasisHead(self.model, bhead.as_mut_ptr()) }));
+        Ok(bhead)
+    }
+
+    /// Compute the `j`-th column of $B^{-1} A$ for the current optimal basis.
+    ///
+    /// The column `j >= NumVars` is the slack of the constraint `j - NumVars`.
+    pub fn binv_col(&self, j: i32) -> Result<SparseVec> {
+        let rows = r#try!(self.check_lp_basis());
+        r#try!(check_index(j, self.vars.len() + rows));
+        let mut x = SparseVec::with_capacity(rows);
+        let mut raw = x.as_raw();
+        r#try!(self.check_apicall(unsafe { ffi::GRBBinvColj(self.model, j, &mut raw) }));
+        x.truncate(raw.len as usize);
+        Ok(x)
+    }
+
+    /// Compute the `i`-th row of $B^{-1} A$ for the current optimal basis.
+    pub fn binv_row(&self, i: i32) -> Result<SparseVec> {
+        let rows = r#try!(self.check_lp_basis());
+        r#try!(check_index(i, rows));
+        let cols = self.vars.len() + rows;
+        let mut x = SparseVec::with_capacity(cols);
+        let mut raw = x.as_raw();
+        r#try!(self.check_apicall(unsafe { ffi::GRBBinvRowi(self.model, i, &mut raw) }));
+        x.truncate(raw.len as usize);
+        Ok(x)
+    }
+
+    /// Solve $B x = b$ for the current optimal basis (forward transformation).
+    pub fn ftran(&self, b: &SparseVec) -> Result<SparseVec> {
+        let rows = r#try!(self.check_lp_basis());
+        r#try!(b.check_indices(rows));
+        let mut b = b.clone();
+        let mut x = SparseVec::with_capacity(rows);
+        let (mut raw_b, mut raw_x) = (b.as_raw(), x.as_raw());
+        r#try!(self.check_apicall(unsafe { ffi::GRBFSolve(self.model, &mut raw_b, &mut raw_x) }));
+        x.truncate(raw_x.len as usize);
+        Ok(x)
+    }
+
+    /// Solve $B^T x = b$ for the current optimal basis (backward transformation).
+    pub fn btran(&self, b: &SparseVec) -> Result<SparseVec> {
+        let rows = r#try!(self.check_lp_basis());
+        r#try!(b.check_indices(rows));
+        let mut b = b.clone();
+        let mut x = SparseVec::with_capacity(rows);
+        let (mut raw_b, mut raw_x) = (b.as_raw(), x.as_raw());
+        r#try!(self.check_apicall(unsafe { ffi::GRBBSolve(self.model, &mut raw_b, &mut raw_x) }));
+        x.truncate(raw_x.len as usize);
+        Ok(x)
+    }
+
+    // check that an optimal simplex basis is available, and return the number of rows.
+    fn check_lp_basis(&self) -> Result<usize> {
+        if r#try!(self.get(attr::IsMIP)) != 0 {
+            return Err(Error::FromAPI(
+                "The advanced simplex routines are not available for MIP models".to_owned(),
+                NOT_FOR_MIP,
+            ));
+        }
+        if r#try!(self.status()) != Status::Optimal {
+            return Err(Error::FromAPI(
+                "The advanced simplex routines require an optimal basis".to_owned(),
+                DATA_NOT_AVAILABLE,
+            ));
+        }
+        Ok(self.constrs.len())
+    }
+}
+
+#[test]
+fn sparse_vec_dense_roundtrip() {
+    let v = SparseVec::from_dense(&[0.0, 1.5, 0.0, -2.0]);
+    assert_eq!(v.indices(), &[1, 3]);
+    assert_eq!(v.values(), &[1.5, -2.0]);
+    assert_eq!(v.to_dense(4), vec![0.0, 1.5, 0.0, -2.0]);
+}
+
+#[test]
+fn sparse_vec_to_dense_should_sum_duplicates_and_ignore_out_of_range() {
+    let v = SparseVec::from_parts(vec![0, 2, 0, 5], vec![1.0, 2.0, 0.5, 3.0]);
+    assert_eq!(v.len(), 4);
+    assert_eq!(v.to_dense(3), vec![1.5, 0.0, 2.0]);
+    assert!(SparseVec::new().is_empty());
+}
+
+#[test]
+fn sparse_vec_indices_should_be_checked_against_rows() {
+    let mut v = SparseVec::from_dense(&[0.0, 1.0, 2.0]);
+    assert!(v.check_indices(3).is_ok());
+    assert!(v.check_indices(2).is_err());
+    v.push(-1, 1.0);
+    assert!(v.check_indices(3).is_err());
+
+    assert!(check_index(0, 1).is_ok());
+    assert!(check_index(1, 1).is_err());
+    assert!(check_index(-1, 1).is_err());
+    assert!(check_index(0, 0).is_err());
+}
+
+#[test]
+fn sparse_vec_should_be_truncated_to_raw_length() {
+    let mut v = SparseVec::with_capacity(4);
+    assert_eq!(v.as_raw().len, 4);
+    v.truncate(1);
+    assert_eq!(v.indices(), &[0]);
+    assert_eq!(v.values(), &[0.0]);
+}
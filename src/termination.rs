@@ -0,0 +1,413 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use super::callback::{MipSolCtx, ProgressCtx, Where};
+use super::handler::CallbackHandler;
+use super::outcome::SolveOutcome;
+use super::trace::{finite, gap};
+use super::{Model, ModelSense, Status};
+use crate::attr;
+use crate::error::Result;
+
+/// Progress of the optimization given to termination policies
+#[derive(Debug, Clone)]
+pub struct Progress {
+    /// Sense of the objective
+    pub sense: ModelSense,
+    /// Elapsed time [sec]
+    pub runtime: f64,
+    /// Objective value of the incumbent
+    pub objective: Option<f64>,
+    /// Best objective bound
+    pub bound: Option<f64>,
+    /// Relative gap between the incumbent and the bound
+    pub gap: Option<f64>,
+    /// Number of explored nodes
+    pub node_count: Option<f64>,
+    /// Elapsed time [sec] when the incumbent was improved last
+    pub last_improvement: Option<f64>,
+    /// `true` if reported from the branch-and-cut (`MIP` or `MIPSol`)
+    pub mip: bool,
+}
+
+impl Progress {
+    fn new(sense: ModelSense) -> Progress {
+        Progress {
+            sense,
+            runtime: 0.0,
+            objective: None,
+            bound: None,
+            gap: None,
+            node_count: None,
+            last_improvement: None,
+            mip: false,
+        }
+    }
+
+    // returns `true` if `a` is strictly better than `b`.
+    fn better(&self, a: f64, b: f64) -> bool {
+        match self.sense {
+            ModelSense::Minimize => a < b,
+            ModelSense::Maximize => a > b,
+        }
+    }
+
+    fn update_incumbent(&mut self, objective: f64) {
+        let improved = match self.objective {
+            Some(current) => self.better(objective, current),
+            None => true,
+        };
+        if improved {
+            self.objective = Some(objective);
+            self.last_improvement = Some(self.runtime);
+        }
+        self.gap = gap(self.objective, self.bound);
+    }
+}
+
+/// Rule to terminate the optimization
+///
+/// A policy is evaluated at each progress report of the solver, and returns the reason to stop
+/// the optimization, or `None` to continue.
+///
+/// # Example
+/// ```ignore
+/// // stop if the gap is below 1% after 60 seconds, or if the incumbent is not improved
+/// // for 5 minutes.
+/// let policy = GapBelow(0.01).and(TimeElapsed(60.0)).or(NoImprovement(300.0));
+/// let outcome = model.optimize_with_policy(policy)?;
+/// if let Some(reason) = outcome.stop_reason {
+///     println!("stopped: {}", reason);
+/// }
+/// ```
+pub trait TerminationPolicy {
+    /// Return the reason to stop the optimization.
+    fn check(&mut self, progress: &Progress) -> Option<String>;
+
+    /// Stop if both policies request it.
+    fn and<P: TerminationPolicy>(self, other: P) -> And<Self, P>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Stop if either policy requests it.
+    fn or<P: TerminationPolicy>(self, other: P) -> Or<Self, P>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+}
+
+impl<F> TerminationPolicy for F
+where
+    F: FnMut(&Progress) -> Option<String>,
+{
+    fn check(&mut self, progress: &Progress) -> Option<String> {
+        self(progress)
+    }
+}
+
+/// Policy which stops if both policies request it
+pub struct And<A, B>(A, B);
+
+impl<A: TerminationPolicy, B: TerminationPolicy> TerminationPolicy for And<A, B> {
+    fn check(&mut self, progress: &Progress) -> Option<String> {
+        // both policies are evaluated, so that stateful policies see every report.
+        match (self.0.check(progress), self.1.check(progress)) {
+            (Some(a), Some(b)) => Some(format!("{} and {}", a, b)),
+            _ => None,
+        }
+    }
+}
+
+/// Policy which stops if either policy requests it
+pub struct Or<A, B>(A, B);
+
+impl<A: TerminationPolicy, B: TerminationPolicy> TerminationPolicy for Or<A, B> {
+    fn check(&mut self, progress: &Progress) -> Option<String> {
+        let a = self.0.check(progress);
+        let b = self.1.check(progress);
+        a.or(b)
+    }
+}
+
+/// Stop after the given time [sec]
+#[derive(Debug, Copy, Clone)]
+pub struct TimeElapsed(pub f64);
+
+impl TerminationPolicy for TimeElapsed {
+    fn check(&mut self, progress: &Progress) -> Option<String> {
+        if progress.runtime >= self.0 {
+            Some(format!("{}s elapsed", self.0))
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop if the relative gap is below the given value
+#[derive(Debug, Copy, Clone)]
+pub struct GapBelow(pub f64);
+
+impl TerminationPolicy for GapBelow {
+    fn check(&mut self, progress: &Progress) -> Option<String> {
+        match progress.gap {
+            Some(gap) if gap < self.0 => Some(format!("gap {} below {}", gap, self.0)),
+            _ => None,
+        }
+    }
+}
+
+/// Stop if the incumbent is not improved for the given time [sec]
+///
+/// The time is measured from the start of the optimization until the first incumbent is found.
+/// The policy is only evaluated in the branch-and-cut, since the incumbent is not tracked in
+/// the Simplex and Barrier reports.
+#[derive(Debug, Copy, Clone)]
+pub struct NoImprovement(pub f64);
+
+impl TerminationPolicy for NoImprovement {
+    fn check(&mut self, progress: &Progress) -> Option<String> {
+        if !progress.mip {
+            return None;
+        }
+        let since = progress.runtime - progress.last_improvement.unwrap_or(0.0);
+        if since >= self.0 {
+            Some(format!("no improvement for {}s", self.0))
+        } else {
+            None
+        }
+    }
+}
+
+/// Stop if the incumbent is at least as good as the given objective value
+#[derive(Debug, Copy, Clone)]
+pub struct ObjectiveTarget(pub f64);
+
+impl TerminationPolicy for ObjectiveTarget {
+    fn check(&mut self, progress: &Progress) -> Option<String> {
+        match progress.objective {
+            Some(obj) if !progress.better(self.0, obj) => {
+                Some(format!("objective {} reached target {}", obj, self.0))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Stop when the flag is set, e.g. from another thread
+///
+/// # Example
+/// ```ignore
+/// let cancel = Cancelled::new();
+/// let flag = cancel.flag();
+/// thread::spawn(move || {
+///     wait_for_user();
+///     flag.store(true, Ordering::SeqCst);
+/// });
+/// model.optimize_with_policy(cancel)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Cancelled(Arc<AtomicBool>);
+
+impl Cancelled {
+    /// Create a policy with a new flag.
+    pub fn new() -> Cancelled {
+        Cancelled::default()
+    }
+
+    /// Create a policy with the given flag.
+    pub fn with_flag(flag: Arc<AtomicBool>) -> Cancelled {
+        Cancelled(flag)
+    }
+
+    /// Retrieve the flag.
+    pub fn flag(&self) -> Arc<AtomicBool> {
+        self.0.clone()
+    }
+}
+
+impl TerminationPolicy for Cancelled {
+    fn check(&mut self, _: &Progress) -> Option<String> {
+        if self.0.load(Ordering::SeqCst) {
+            Some("cancelled".to_owned())
+        } else {
+            None
+        }
+    }
+}
+
+/// Callback handler which terminates the optimization by a policy
+///
+/// The policy is evaluated at the `Simplex`, `Barrier`, `MIP` and `MIPSol` locations.
+pub struct Termination<P: TerminationPolicy> {
+    policy: P,
+    progress: Progress,
+    reason: Option<String>,
+}
+
+impl<P: TerminationPolicy> Termination<P> {
+    /// Create a handler for a model with the given objective sense.
+    pub fn new(policy: P, sense: ModelSense) -> Termination<P> {
+        Termination {
+            policy,
+            progress: Progress::new(sense),
+            reason: None,
+        }
+    }
+
+    /// Retrieve the reason why the optimization was terminated.
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Retrieve the last progress given to the policy.
+    pub fn progress(&self) -> &Progress {
+        &self.progress
+    }
+
+    /// Consume the handler and retrieve the reason why the optimization was terminated.
+    pub fn into_reason(self) -> Option<String> {
+        self.reason
+    }
+
+    fn evaluate(&mut self, runtime: f64, mip: bool) -> bool {
+        self.progress.runtime = runtime;
+        self.progress.mip = mip;
+        if self.reason.is_none() {
+            self.reason = self.policy.check(&self.progress);
+        }
+        self.reason.is_some()
+    }
+}
+
+impl<P: TerminationPolicy> CallbackHandler for Termination<P> {
+    fn on_simplex(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        if self.evaluate(r#try!(ctx.runtime()), false) {
+            ctx.terminate();
+        }
+        Ok(())
+    }
+
+    fn on_barrier(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        if self.evaluate(r#try!(ctx.runtime()), false) {
+            ctx.terminate();
+        }
+        Ok(())
+    }
+
+    fn on_mip(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        let runtime = r#try!(ctx.runtime());
+        if let Where::MIP {
+            objbst,
+            objbnd,
+            nodcnt,
+            ..
+        } = *ctx.info()
+        {
+            self.progress.runtime = runtime;
+            self.progress.bound = finite(objbnd);
+            self.progress.node_count = Some(nodcnt);
+            if let Some(objbst) = finite(objbst) {
+                self.progress.update_incumbent(objbst);
+            }
+            self.progress.gap = gap(self.progress.objective, self.progress.bound);
+        }
+        if self.evaluate(runtime, true) {
+            ctx.terminate();
+        }
+        Ok(())
+    }
+
+    fn on_mip_sol(&mut self, ctx: &MipSolCtx) -> Result<()> {
+        let runtime = r#try!(ctx.runtime());
+        if let Where::MIPSol {
+            obj,
+            objbnd,
+            nodcnt,
+            ..
+        } = *ctx.info()
+        {
+            self.progress.runtime = runtime;
+            self.progress.bound = finite(objbnd);
+            self.progress.node_count = Some(nodcnt);
+            self.progress.update_incumbent(obj);
+        }
+        if self.evaluate(runtime, true) {
+            ctx.terminate();
+        }
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Optimize the model until the policy requests to stop, and summarize the result.
+    ///
+    /// If the policy terminated the optimization, the status is `Interrupted` and the reason is
+    /// reported as `stop_reason` of the outcome.
+    pub fn optimize_with_policy<P: TerminationPolicy>(
+        &mut self,
+        policy: P,
+    ) -> Result<SolveOutcome> {
+        r#try!(self.update());
+        let sense = if r#try!(self.get(attr::ModelSense)) < 0 {
+            ModelSense::Maximize
+        } else {
+            ModelSense::Minimize
+        };
+        let mut termination = Termination::new(policy, sense);
+        r#try!(self.optimize_with_handler(&mut termination));
+
+        let mut outcome = r#try!(self.outcome());
+        if outcome.status == Status::Interrupted {
+            outcome.stop_reason = termination.into_reason();
+        }
+        Ok(outcome)
+    }
+}
+
+#[test]
+fn termination_policies_should_be_combined() {
+    let mut progress = Progress::new(ModelSense::Minimize);
+    progress.mip = true;
+    progress.runtime = 30.0;
+    progress.bound = Some(99.5);
+    progress.update_incumbent(100.0);
+    assert_eq!(progress.gap, Some(0.005));
+
+    let mut policy = GapBelow(0.01).and(TimeElapsed(60.0));
+    assert_eq!(policy.check(&progress), None);
+    progress.runtime = 60.0;
+    assert_eq!(
+        policy.check(&progress),
+        Some("gap 0.005 below 0.01 and 60s elapsed".to_owned())
+    );
+
+    let cancel = Cancelled::new();
+    let mut policy = NoImprovement(300.0)
+        .or(ObjectiveTarget(90.0))
+        .or(cancel.clone());
+    assert_eq!(policy.check(&progress), None);
+    cancel.flag().store(true, Ordering::SeqCst);
+    assert_eq!(policy.check(&progress), Some("cancelled".to_owned()));
+
+    progress.runtime = 400.0;
+    progress.update_incumbent(101.0);
+    assert_eq!(progress.last_improvement, Some(30.0));
+    assert_eq!(
+        NoImprovement(300.0).check(&progress),
+        Some("no improvement for 300s".to_owned())
+    );
+    progress.mip = false;
+    assert_eq!(NoImprovement(300.0).check(&progress), None);
+    progress.update_incumbent(90.0);
+    assert!(ObjectiveTarget(90.0).check(&progress).is_some());
+}
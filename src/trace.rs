@@ -0,0 +1,494 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use itertools::Itertools;
+use std::fmt;
+use std::io::Write;
+
+use super::callback::{MipSolCtx, ProgressCtx, Where};
+use super::handler::CallbackHandler;
+use super::{Model, ModelSense, Var};
+use crate::attr;
+use crate::error::Result;
+use crate::INFINITY;
+
+/// Algorithm which reported a trace point
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TraceEvent {
+    /// Simplex (`objective` is the current simplex objective)
+    Simplex,
+    /// Barrier (`objective` and `bound` are the primal and dual objectives)
+    Barrier,
+    /// Branch-and-cut (`objective` and `bound` are the incumbent and the best bound)
+    MIP,
+    /// A new incumbent solution
+    Incumbent,
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            TraceEvent::Simplex => "simplex",
+            TraceEvent::Barrier => "barrier",
+            TraceEvent::MIP => "mip",
+            TraceEvent::Incumbent => "incumbent",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Progress of the optimization at a certain time
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TracePoint {
+    /// Elapsed time [sec]
+    pub time: f64,
+    /// Algorithm which reported the progress
+    pub event: TraceEvent,
+    /// Primal objective value
+    pub objective: Option<f64>,
+    /// Objective bound
+    pub bound: Option<f64>,
+    /// Relative gap between `objective` and `bound`
+    pub gap: Option<f64>,
+    /// Number of explored nodes
+    pub node_count: Option<f64>,
+}
+
+/// Improved incumbent solution
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TraceIncumbent {
+    /// Elapsed time [sec]
+    pub time: f64,
+    /// Objective value of the solution
+    pub objective: f64,
+    /// Number of explored nodes when the solution was found
+    pub node_count: f64,
+    /// Values of the variables, in the order of `Model::get_vars()` (empty unless recorded)
+    pub values: Vec<f64>,
+}
+
+/// History of the incumbents and bounds of an optimization
+///
+/// Created by [`TraceRecorder`](struct.TraceRecorder.html) or
+/// [`Model::optimize_traced()`](struct.Model.html#method.optimize_traced).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolveTrace {
+    points: Vec<TracePoint>,
+    incumbents: Vec<TraceIncumbent>,
+}
+
+impl SolveTrace {
+    /// Retrieve the progress points, in chronological order.
+    pub fn points(&self) -> &[TracePoint] {
+        &self.points
+    }
+
+    /// Retrieve the improved incumbent solutions, in chronological order.
+    pub fn incumbents(&self) -> &[TraceIncumbent] {
+        &self.incumbents
+    }
+
+    /// Write the progress points as CSV, with a header line.
+    pub fn write_csv<W: Write>(&self, mut w: W) -> Result<()> {
+        r#try!(writeln!(w, "time,event,objective,bound,gap,node_count"));
+        for p in &self.points {
+            r#try!(writeln!(
+                w,
+                "{},{},{},{},{},{}",
+                p.time,
+                p.event,
+                Csv(p.objective),
+                Csv(p.bound),
+                Csv(p.gap),
+                Csv(p.node_count)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Write the progress points and the incumbents as a JSON object.
+    pub fn write_json<W: Write>(&self, mut w: W) -> Result<()> {
+        r#try!(write!(w, "{{\"points\":["));
+        for (i, p) in self.points.iter().enumerate() {
+            r#try!(write!(
+                w,
+                "{}{{\"time\":{},\"event\":{},\"objective\":{},\"bound\":{},\"gap\":{},\
+                 \"node_count\":{}}}",
+                if i > 0 { "," } else { "" },
+                Json(Some(p.time)),
+                JsonStr(&p.event.to_string()),
+                Json(p.objective),
+                Json(p.bound),
+                Json(p.gap),
+                Json(p.node_count)
+            ));
+        }
+        r#try!(write!(w, "],\"incumbents\":["));
+        for (i, inc) in self.incumbents.iter().enumerate() {
+            r#try!(write!(
+                w,
+                "{}{{\"time\":{},\"objective\":{},\"node_count\":{},\"values\":[{}]}}",
+                if i > 0 { "," } else { "" },
+                Json(Some(inc.time)),
+                Json(Some(inc.objective)),
+                Json(Some(inc.node_count)),
+                inc.values.iter().map(|&v| Json(Some(v))).join(",")
+            ));
+        }
+        r#try!(write!(w, "]}}"));
+        Ok(())
+    }
+}
+
+struct Csv(Option<f64>);
+
+impl fmt::Display for Csv {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(value) => write!(f, "{}", value),
+            None => Ok(()),
+        }
+    }
+}
+
+struct Json(Option<f64>);
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(value) if value.is_finite() => write!(f, "{:?}", value),
+            _ => write!(f, "null"),
+        }
+    }
+}
+
+struct JsonStr<'a>(&'a str);
+
+impl<'a> fmt::Display for JsonStr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        r#try!(write!(f, "\""));
+        for c in self.0.chars() {
+            match c {
+                '"' => r#try!(write!(f, "\\\"")),
+                '\\' => r#try!(write!(f, "\\\\")),
+                '\n' => r#try!(write!(f, "\\n")),
+                '\r' => r#try!(write!(f, "\\r")),
+                '\t' => r#try!(write!(f, "\\t")),
+                c if (c as u32) < 0x20 => r#try!(write!(f, "\\u{:04x}", c as u32)),
+                c => r#try!(write!(f, "{}", c)),
+            }
+        }
+        write!(f, "\"")
+    }
+}
+
+// values reported by Gurobi beyond the infinity are treated as missing.
+pub(crate) fn finite(value: f64) -> Option<f64> {
+    if value.abs() < INFINITY {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+// relative gap in the same way as `MIPGap`.
+pub(crate) fn gap(objective: Option<f64>, bound: Option<f64>) -> Option<f64> {
+    match (objective, bound) {
+        (Some(obj), Some(bnd)) if obj == bnd => Some(0.0),
+        (Some(obj), Some(bnd)) if obj != 0.0 => Some((obj - bnd).abs() / obj.abs()),
+        _ => None,
+    }
+}
+
+/// Callback handler which records a [`SolveTrace`](struct.SolveTrace.html)
+///
+/// A progress point is recorded whenever the objective or the bound reported by an algorithm
+/// changes, but not more often than the sampling interval.
+///
+/// # Example
+/// ```ignore
+/// let mut recorder = TraceRecorder::new(Minimize).interval(0.5).record_values(true);
+/// model.optimize_with_handler(&mut recorder)?;
+/// recorder.into_trace().write_csv(File::create("trace.csv")?)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TraceRecorder {
+    sense: f64,
+    interval: f64,
+    record_values: bool,
+    vars: Vec<Var>,
+    trace: SolveTrace,
+    // index of the last point of each event in `trace.points`.
+    last: [Option<usize>; 4],
+}
+
+impl TraceRecorder {
+    /// Create a recorder for a model with the given objective sense.
+    pub fn new(sense: ModelSense) -> TraceRecorder {
+        TraceRecorder {
+            sense: match sense {
+                ModelSense::Minimize => 1.0,
+                ModelSense::Maximize => -1.0,
+            },
+            interval: 0.0,
+            record_values: false,
+            vars: Vec::new(),
+            trace: SolveTrace::default(),
+            last: [None; 4],
+        }
+    }
+
+    /// Set the minimum interval [sec] between progress points of the same algorithm.
+    pub fn interval(mut self, interval: f64) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Record the values of all variables at each improved incumbent.
+    pub fn record_values(mut self, record_values: bool) -> Self {
+        self.record_values = record_values;
+        self
+    }
+
+    /// Retrieve the trace recorded so far.
+    pub fn trace(&self) -> &SolveTrace {
+        &self.trace
+    }
+
+    /// Consume the recorder and retrieve the trace.
+    pub fn into_trace(self) -> SolveTrace {
+        self.trace
+    }
+
+    fn record(&mut self, point: TracePoint) {
+        let event = point.event as usize;
+        if let Some(last) = self.last[event].map(|i| &self.trace.points[i]) {
+            let unchanged = last.objective == point.objective && last.bound == point.bound;
+            if unchanged || point.time - last.time < self.interval {
+                return;
+            }
+        }
+        self.last[event] = Some(self.trace.points.len());
+        self.trace.points.push(point);
+    }
+}
+
+impl CallbackHandler for TraceRecorder {
+    fn on_simplex(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        if let Where::Simplex { objval, .. } = *ctx.info() {
+            let point = TracePoint {
+                time: r#try!(ctx.runtime()),
+                event: TraceEvent::Simplex,
+                objective: finite(objval),
+                bound: None,
+                gap: None,
+                node_count: None,
+            };
+            self.record(point);
+        }
+        Ok(())
+    }
+
+    fn on_barrier(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        if let Where::Barrier {
+            primobj, dualobj, ..
+        } = *ctx.info()
+        {
+            let (objective, bound) = (finite(primobj), finite(dualobj));
+            let point = TracePoint {
+                time: r#try!(ctx.runtime()),
+                event: TraceEvent::Barrier,
+                objective,
+                bound,
+                gap: gap(objective, bound),
+                node_count: None,
+            };
+            self.record(point);
+        }
+        Ok(())
+    }
+
+    fn on_mip(&mut self, ctx: &ProgressCtx) -> Result<()> {
+        if let Where::MIP {
+            objbst,
+            objbnd,
+            nodcnt,
+            ..
+        } = *ctx.info()
+        {
+            let (objective, bound) = (finite(objbst), finite(objbnd));
+            let point = TracePoint {
+                time: r#try!(ctx.runtime()),
+                event: TraceEvent::MIP,
+                objective,
+                bound,
+                gap: gap(objective, bound),
+                node_count: Some(nodcnt),
+            };
+            self.record(point);
+        }
+        Ok(())
+    }
+
+    fn on_mip_sol(&mut self, ctx: &MipSolCtx) -> Result<()> {
+        let (obj, objbnd, nodcnt) = match *ctx.info() {
+            Where::MIPSol {
+                obj,
+                objbnd,
+                nodcnt,
+                ..
+            } => (obj, objbnd, nodcnt),
+            _ => return Ok(()),
+        };
+        let improved = match self.trace.incumbents.last() {
+            Some(last) => self.sense * obj < self.sense * last.objective,
+            None => true,
+        };
+        if !improved {
+            return Ok(());
+        }
+
+        let time = r#try!(ctx.runtime());
+        let values = if self.record_values {
+            if self.vars.is_empty() {
+                self.vars = ctx.model().get_vars().cloned().collect_vec();
+            }
+            r#try!(ctx.solution(&self.vars))
+        } else {
+            Vec::new()
+        };
+        let bound = finite(objbnd);
+        self.trace.points.push(TracePoint {
+            time,
+            event: TraceEvent::Incumbent,
+            objective: Some(obj),
+            bound,
+            gap: gap(Some(obj), bound),
+            node_count: Some(nodcnt),
+        });
+        self.trace.incumbents.push(TraceIncumbent {
+            time,
+            objective: obj,
+            node_count: nodcnt,
+            values,
+        });
+        Ok(())
+    }
+}
+
+impl Model {
+    /// Optimize the model while recording the history of the incumbents and bounds.
+    ///
+    /// The values of the variables are not recorded; use a
+    /// [`TraceRecorder`](struct.TraceRecorder.html) with `record_values(true)` to keep them.
+    pub fn optimize_traced(&mut self) -> Result<SolveTrace> {
+        r#try!(self.update());
+        let sense = if r#try!(self.get(attr::ModelSense)) < 0 {
+            ModelSense::Maximize
+        } else {
+            ModelSense::Minimize
+        };
+        let mut recorder = TraceRecorder::new(sense);
+        r#try!(self.optimize_with_handler(&mut recorder));
+        Ok(recorder.into_trace())
+    }
+}
+
+#[test]
+fn trace_should_be_written_as_csv_and_json() {
+    let trace = SolveTrace {
+        points: vec![
+            TracePoint {
+                time: 0.5,
+                event: TraceEvent::MIP,
+                objective: None,
+                bound: Some(1.5),
+                gap: None,
+                node_count: Some(0.0),
+            },
+            TracePoint {
+                time: 1.0,
+                event: TraceEvent::Incumbent,
+                objective: Some(2.0),
+                bound: Some(1.5),
+                gap: gap(Some(2.0), Some(1.5)),
+                node_count: Some(3.0),
+            },
+        ],
+        incumbents: vec![TraceIncumbent {
+            time: 1.0,
+            objective: 2.0,
+            node_count: 3.0,
+            values: vec![1.0, 0.0],
+        }],
+    };
+
+    let mut csv = Vec::new();
+    trace.write_csv(&mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "time,event,objective,bound,gap,node_count\n\
+         0.5,mip,,1.5,,0\n\
+         1,incumbent,2,1.5,0.25,3\n"
+    );
+
+    let mut json = Vec::new();
+    trace.write_json(&mut json).unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        "{\"points\":[\
+         {\"time\":0.5,\"event\":\"mip\",\"objective\":null,\"bound\":1.5,\"gap\":null,\
+         \"node_count\":0.0},\
+         {\"time\":1.0,\"event\":\"incumbent\",\"objective\":2.0,\"bound\":1.5,\"gap\":0.25,\
+         \"node_count\":3.0}],\
+         \"incumbents\":[{\"time\":1.0,\"objective\":2.0,\"node_count\":3.0,\"values\":[1.0,0.0]}]}"
+    );
+}
+
+#[test]
+fn json_strings_should_be_escaped() {
+    assert_eq!(JsonStr("mip").to_string(), "\"mip\"");
+    assert_eq!(
+        JsonStr("a\"b\\c\nd\u{1}").to_string(),
+        "\"a\\\"b\\\\c\\nd\\u0001\""
+    );
+}
+
+#[test]
+fn trace_points_should_be_sampled_per_event() {
+    let point = |time: f64, event: TraceEvent, objective: f64| TracePoint {
+        time,
+        event,
+        objective: Some(objective),
+        bound: None,
+        gap: None,
+        node_count: None,
+    };
+    let mut recorder = TraceRecorder::new(ModelSense::Minimize).interval(1.0);
+    recorder.record(point(0.0, TraceEvent::Simplex, 3.0));
+    recorder.record(point(0.5, TraceEvent::Barrier, 3.0));
+    recorder.record(point(0.5, TraceEvent::Simplex, 2.0));
+    recorder.record(point(1.5, TraceEvent::Simplex, 2.0));
+    recorder.record(point(1.5, TraceEvent::Barrier, 3.0));
+    recorder.record(point(2.0, TraceEvent::Simplex, 1.0));
+    let times = recorder
+        .trace()
+        .points()
+        .iter()
+        .map(|p| (p.event, p.time))
+        .collect_vec();
+    assert_eq!(
+        times,
+        vec![
+            (TraceEvent::Simplex, 0.0),
+            (TraceEvent::Barrier, 0.5),
+            (TraceEvent::Simplex, 1.5),
+        ]
+    );
+}
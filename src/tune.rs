@@ -0,0 +1,190 @@
+// Copyright (c) 2016 Yusuke Sasaki
+//
+// This software is released under the MIT License.
+// See http://opensource.org/licenses/mit-license.php or <LICENSE>.
+
+use crate::ffi;
+use itertools::Itertools;
+
+use std::ptr::null_mut;
+use std::slice::Iter;
+
+use super::Model;
+use crate::error::{Error, Result};
+use crate::parameter::ParamSet;
+use crate::{attr, param};
+
+/// Options of the automated parameter tuning
+///
+/// Each option corresponds to a tuning parameter (`TuneResults`, `TuneTimeLimit`, `TuneTrials`,
+/// `TuneOutput` and `TuneJobs`). Options which are not given keep the values of the environment.
+/// The options are only set during the tuning, and the previous values are restored after it.
+#[derive(Debug, Clone, Default)]
+pub struct TuneOptions {
+    results: Option<i32>,
+    time_limit: Option<f64>,
+    trials: Option<i32>,
+    output: Option<i32>,
+    jobs: Option<i32>,
+}
+
+impl TuneOptions {
+    /// Create the options with default values.
+    pub fn new() -> TuneOptions {
+        TuneOptions::default()
+    }
+
+    /// Set the number of improved parameter sets to be returned.
+    pub fn results(mut self, results: i32) -> Self {
+        self.results = Some(results);
+        self
+    }
+
+    /// Set the time limit [sec] of the whole tuning.
+    pub fn time_limit(mut self, time_limit: f64) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Set the number of trials with each parameter set.
+    pub fn trials(mut self, trials: i32) -> Self {
+        self.trials = Some(trials);
+        self
+    }
+
+    /// Set the amount of output produced by the tuning (0 to 3).
+    pub fn output(mut self, output: i32) -> Self {
+        self.output = Some(output);
+        self
+    }
+
+    /// Set the number of distributed tuning jobs.
+    pub fn jobs(mut self, jobs: i32) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    // the tuning parameters to be set during the tuning.
+    fn params(&self) -> ParamSet {
+        let mut params = ParamSet::new();
+        if let Some(results) = self.results {
+            params.insert(param::TuneResults, results);
+        }
+        if let Some(time_limit) = self.time_limit {
+            params.insert(param::TuneTimeLimit, time_limit);
+        }
+        if let Some(trials) = self.trials {
+            params.insert(param::TuneTrials, trials);
+        }
+        if let Some(output) = self.output {
+            params.insert(param::TuneOutput, output);
+        }
+        if let Some(jobs) = self.jobs {
+            params.insert(param::TuneJobs, jobs);
+        }
+        params
+    }
+}
+
+/// Results of the automated parameter tuning
+///
+/// Each result holds the parameters changed by the tuning tool, relative to the environment of
+/// the tuned model. Results are sorted from best to worst.
+///
+/// # Example
+/// ```ignore
+/// let results = model.tune_with(&TuneOptions::new().time_limit(60.0))?;
+/// if let Some(best) = results.best() {
+///     model.get_env_mut().apply(best)?;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TuneResults {
+    results: Vec<ParamSet>,
+}
+
+impl TuneResults {
+    /// Number of the parameter sets found by the tuning (`TuneResultCount`).
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns `true` if the tuning found no improved parameter sets.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// Retrieve the `n`-th parameter set.
+    pub fn get(&self, n: usize) -> Option<&ParamSet> {
+        self.results.get(n)
+    }
+
+    /// Retrieve the best parameter set.
+    pub fn best(&self) -> Option<&ParamSet> {
+        self.results.first()
+    }
+
+    /// Retrieve an iterator of the parameter sets.
+    pub fn iter(&self) -> Iter<ParamSet> {
+        self.results.iter()
+    }
+}
+
+impl Model {
+    /// Perform the automated parameter tuning on the model and retrieve its results.
+    ///
+    /// The parameters of the model's environment, including the tuning options, are kept as
+    /// they were before the tuning.
+    pub fn tune_with(&mut self, options: &TuneOptions) -> Result<TuneResults> {
+        r#try!(self.update());
+        let mut model = r#try!(self.with_params(options.params()));
+        r#try!(model.tune());
+        let results = r#try!(model.tune_results());
+        r#try!(model.restore());
+        Ok(results)
+    }
+
+    /// Perform the automated parameter tuning on a set of models.
+    ///
+    /// The tuning options are set to the environment of the first model during the tuning, and
+    /// the results are relative to it.
+    pub fn tune_models(models: &mut [&mut Model], options: &TuneOptions) -> Result<TuneResults> {
+        if models.is_empty() {
+            return Err(Error::InconsitentDims);
+        }
+        for model in models.iter_mut() {
+            r#try!(model.update());
+        }
+        let mut ptrs = models.iter().map(|m| m.model).collect_vec();
+
+        let mut first = r#try!(models[0].with_params(options.params()));
+        r#try!(first.check_apicall(unsafe {
+            ffi::GRBtunemodels(
+                ptrs.len() as ffi::c_int,
+                ptrs.as_mut_ptr(),
+                null_mut(),
+                null_mut(),
+            )
+        }));
+        let results = r#try!(first.tune_results());
+        r#try!(first.restore());
+        Ok(results)
+    }
+
+    // collect the parameter sets found by the last tuning.
+    fn tune_results(&mut self) -> Result<TuneResults> {
+        let count = r#try!(self.get(attr::TuneResultCount));
+        let base = self.get_env().snapshot_params();
+
+        let mut results = Vec::with_capacity(count as usize);
+        for n in 0..count {
+            r#try!(self.get_tune_result(n));
+            let tuned = self.get_env().snapshot_params().diff(&base);
+            // restore the parameters loaded by `get_tune_result()`.
+            r#try!(self.get_env_mut().apply(&base.restrict(&tuned)));
+            results.push(tuned);
+        }
+
+        Ok(TuneResults { results })
+    }
+}